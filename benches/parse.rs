@@ -0,0 +1,112 @@
+//! Compares `parse::ints` with the `split` and `parse` parsers that days 04 and 09 used before.
+//! Run with `cargo bench --bench parse`.
+use std::{
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use advent_of_code::parse::{ints, ints_per_line};
+
+const LINES: u32 = 200;
+const ITERATIONS: u32 = 2000;
+
+/// Lines like day 04's `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`.
+fn cards_input() -> String {
+    (1..=LINES).fold(String::new(), |mut input, card| {
+        let number = |i: u32| (card * 37 + i * 53) % 99 + 1;
+        let winning = (0..10).map(|i| format!("{:>2}", number(i)));
+        let yours = (10..35).map(|i| format!("{:>2}", number(i)));
+
+        let _ = writeln!(
+            input,
+            "Card {card:>3}: {} | {}",
+            winning.collect::<Vec<_>>().join(" "),
+            yours.collect::<Vec<_>>().join(" ")
+        );
+        input
+    })
+}
+
+/// Lines like day 09's `10 13 16 21 30 45`, with negative values.
+fn histories_input() -> String {
+    (1..=LINES).fold(String::new(), |mut input, line| {
+        let values = (0..21).map(|i| {
+            let value = i64::from(line * 7919 + i * i * 31) % 2_000_000 - 1_000_000;
+            value.to_string()
+        });
+
+        let _ = writeln!(input, "{}", values.collect::<Vec<_>>().join(" "));
+        input
+    })
+}
+
+fn old_cards(input: &str) -> Vec<(Vec<u32>, Vec<u32>)> {
+    input
+        .lines()
+        .map(|line| line.split(": ").nth(1).expect("Invalid input"))
+        .map(|line| line.split(" | ").collect::<Vec<&str>>())
+        .map(|parts| {
+            let parse = |part: &str| {
+                part.split(' ')
+                    .filter(|s| !s.is_empty())
+                    .map(|n| n.trim().parse().expect("Invalid input"))
+                    .collect::<Vec<u32>>()
+            };
+            (parse(parts[0]), parse(parts[1]))
+        })
+        .collect()
+}
+
+fn new_cards(input: &str) -> Vec<(Vec<u32>, Vec<u32>)> {
+    input
+        .lines()
+        .map(|line| line.split(": ").nth(1).expect("Invalid input"))
+        .map(|line| line.split_once(" | ").expect("Invalid input"))
+        .map(|(winning, yours)| (ints(winning).collect(), ints(yours).collect()))
+        .collect()
+}
+
+fn old_histories(input: &str) -> Vec<Vec<i64>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|value| value.parse::<i64>().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+fn new_histories(input: &str) -> Vec<Vec<i64>> {
+    ints_per_line::<i64>(input).map(Iterator::collect).collect()
+}
+
+/// The mean time of one call of `parser`.
+fn measure<T>(input: &str, parser: impl Fn(&str) -> T) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(parser(black_box(input)));
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn compare<T: PartialEq + std::fmt::Debug>(
+    name: &str,
+    input: &str,
+    old: impl Fn(&str) -> T,
+    new: impl Fn(&str) -> T,
+) {
+    assert_eq!(old(input), new(input), "the parsers of {name} disagree");
+
+    let (old, new) = (measure(input, old), measure(input, new));
+    println!(
+        "{name}: split and parse {old:.1?}, ints {new:.1?} ({:.2}x)",
+        old.as_secs_f64() / new.as_secs_f64()
+    );
+}
+
+fn main() {
+    compare("day 04", &cards_input(), old_cards, new_cards);
+    compare("day 09", &histories_input(), old_histories, new_histories);
+}
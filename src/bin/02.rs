@@ -1,3 +1,6 @@
+use advent_of_code::template::params::Params;
+
+/// Defaults for the `red`, `green` and `blue` parameters.
 const MAX_RED_CUBES: usize = 12;
 const MAX_GREEN_CUBES: usize = 13;
 const MAX_BLUE_CUBES: usize = 14;
@@ -82,17 +85,20 @@ fn parse_games(input: &str) -> Vec<Game> {
         .collect::<Vec<Game>>()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let games = parse_games(input);
+    let max_red = params.get_or("red", MAX_RED_CUBES);
+    let max_green = params.get_or("green", MAX_GREEN_CUBES);
+    let max_blue = params.get_or("blue", MAX_BLUE_CUBES);
 
     let valid_game_ids = games
         .iter()
         .filter(|game| {
             game.rounds.iter().all(|round| {
                 round.cubes.iter().all(|cube| match cube.color {
-                    Color::Red => cube.count <= MAX_RED_CUBES,
-                    Color::Green => cube.count <= MAX_GREEN_CUBES,
-                    Color::Blue => cube.count <= MAX_BLUE_CUBES,
+                    Color::Red => cube.count <= max_red,
+                    Color::Green => cube.count <= max_green,
+                    Color::Blue => cube.count <= max_blue,
                 })
             })
         })
@@ -102,7 +108,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(valid_game_ids.iter().sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, _params: &Params) -> Option<u32> {
     let games = parse_games(input);
 
     let products = games
@@ -130,7 +136,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(products.iter().sum::<usize>() as u32)
 }
 
-advent_of_code::main!(2);
+advent_of_code::main!(2, params);
 
 #[cfg(test)]
 mod tests {
@@ -138,13 +144,29 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 2));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", 2),
+            &Params::default(),
+        );
         assert_eq!(result, Some(8));
     }
 
+    #[test]
+    fn test_part_one_with_params() {
+        let params = Params::default()
+            .with("red", 20)
+            .with("green", 13)
+            .with("blue", 15);
+        let result = part_one(&advent_of_code::template::read_file("examples", 2), &params);
+        assert_eq!(result, Some(1 + 2 + 3 + 4 + 5));
+    }
+
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 2));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", 2),
+            &Params::default(),
+        );
         assert_eq!(result, Some(2286));
     }
 }
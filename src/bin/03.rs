@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use advent_of_code::template::viz::{Color, Frame, Style, Visualizer};
+
 struct Part {
     symbol: char,
     position: String,
@@ -95,7 +97,52 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-advent_of_code::main!(3);
+/// Shows which numbers count as part numbers (green) and which do not (red).
+fn visualize(input: &str, viz: &mut Visualizer) {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut frame = Frame::from_text(input).styled(Style::fg(Color::Gray));
+    let (mut counted, mut skipped) = (0, 0);
+
+    for (row, line) in lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut col = 0;
+
+        while col < chars.len() {
+            if !chars[col].is_ascii_digit() {
+                if chars[col] != '.' {
+                    frame.set_style((row, col), Style::fg(Color::Yellow).bold());
+                }
+                col += 1;
+                continue;
+            }
+
+            let end = (col..chars.len())
+                .find(|&c| !chars[c].is_ascii_digit())
+                .unwrap_or(chars.len());
+            let is_part = (col..end).any(|c| parse_part(&lines, c, row).is_some());
+
+            let style = if is_part {
+                counted += 1;
+                Style::fg(Color::Green).bold()
+            } else {
+                skipped += 1;
+                Style::fg(Color::Red)
+            };
+
+            for c in col..end {
+                frame.set_style((row, c), style);
+            }
+            col = end;
+        }
+    }
+
+    frame.set_caption(format!(
+        "{counted} part numbers next to a symbol, {skipped} other numbers"
+    ));
+    viz.emit(&frame);
+}
+
+advent_of_code::main!(3, viz);
 
 #[cfg(test)]
 mod tests {
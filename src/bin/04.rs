@@ -1,5 +1,5 @@
+use advent_of_code::{memoize::memoize_fast, parse::ints};
 use rayon::prelude::*;
-use std::collections::{HashMap, VecDeque};
 
 #[derive(Debug, Eq, PartialEq, Hash)]
 struct Card {
@@ -35,24 +35,10 @@ impl Card {
         input
             .lines()
             .map(|line| line.split(": ").nth(1).expect("Invalid input"))
-            .map(|line| line.split(" | ").collect::<Vec<&str>>())
-            .map(|parts| {
-                let winning_numbers = parts[0]
-                    .split(" ")
-                    .filter(|s| !s.is_empty())
-                    .map(|n| n.trim().parse().expect("Invalid input"))
-                    .collect::<Vec<u32>>();
-
-                let your_numbers = parts[1]
-                    .split(" ")
-                    .filter(|s| !s.is_empty())
-                    .map(|n| n.trim().parse().expect("Invalid input"))
-                    .collect::<Vec<u32>>();
-
-                return Card {
-                    winning_numbers,
-                    your_numbers,
-                };
+            .map(|line| line.split_once(" | ").expect("Invalid input"))
+            .map(|(winning, yours)| Card {
+                winning_numbers: ints(winning).collect(),
+                your_numbers: ints(yours).collect(),
             })
             .collect::<Vec<Card>>()
     }
@@ -68,27 +54,18 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let cards = Card::parse_cards(input);
-    let mut counts: HashMap<usize, u32> = HashMap::new();
-    let mut match_memo: HashMap<usize, usize> = HashMap::new();
-    let mut backlog: VecDeque<usize> = (0..cards.len()).collect();
-
-    while let Some(card_index) = backlog.pop_front() {
-        let matches = *match_memo
-            .entry(card_index)
-            .or_insert_with(|| cards[card_index].get_matches() as usize);
-
-        *counts.entry(card_index).or_insert(0) += 1;
-
-        for i in 1..=matches {
-            let next_card_index = card_index + i;
-            if next_card_index < cards.len() {
-                backlog.push_back(next_card_index);
-            }
-        }
-    }
-
-    Some(counts.values().sum())
+    let matches = Card::parse_cards(input)
+        .iter()
+        .map(|card| card.get_matches() as usize)
+        .collect::<Vec<usize>>();
+
+    // Number of cards a single copy of card `i` ends up as, including itself.
+    let mut total_cards = memoize_fast(|total_cards: &mut dyn FnMut(usize) -> u32, i: usize| {
+        let won = i + 1..(i + 1 + matches[i]).min(matches.len());
+        1 + won.map(total_cards).sum::<u32>()
+    });
+
+    Some((0..matches.len()).map(|i| total_cards.call(i)).sum())
 }
 
 advent_of_code::main!(4);
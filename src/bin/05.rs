@@ -234,6 +234,7 @@ advent_of_code::main!(5);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{check, shrink_number, shrink_vec, Generator, Rng};
 
     #[test]
     fn test_part_one() {
@@ -246,4 +247,156 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", 5));
         assert_eq!(result, Some(46));
     }
+
+    #[derive(Debug, Clone)]
+    struct Almanac {
+        /// Pairs of numbers on the seeds line, read as `(start, length)` in part two.
+        seeds: Vec<(u64, u64)>,
+        /// Entries of each map as `(target start, source start, length)`.
+        maps: Vec<Vec<(u64, u64, u64)>>,
+    }
+
+    impl Almanac {
+        /// The source ranges of a map must not overlap.
+        fn is_valid(&self) -> bool {
+            self.maps.iter().all(|map| {
+                let mut sources = map.iter().map(|e| (e.1, e.1 + e.2)).collect_vec();
+                sources.sort_unstable();
+                sources.windows(2).all(|w| w[0].1 <= w[1].0)
+            })
+        }
+    }
+
+    /// Small almanacs with up to seven maps, so that every seed range can also be mapped seed by seed.
+    struct Almanacs;
+
+    impl Generator for Almanacs {
+        type Value = Almanac;
+
+        fn generate(&self, rng: &mut Rng) -> Self::Value {
+            let seeds = (0..rng.between(1, 4))
+                .map(|_| (rng.below(100), rng.between(1, 20)))
+                .collect();
+
+            let maps = (0..rng.between(1, 7))
+                .map(|_| {
+                    let mut next_source = rng.below(10);
+                    (0..rng.between(1, 5))
+                        .map(|_| {
+                            let source = next_source + rng.below(15);
+                            let length = rng.between(1, 20);
+                            next_source = source + length;
+                            (rng.below(120), source, length)
+                        })
+                        .collect()
+                })
+                .collect();
+
+            Almanac { seeds, maps }
+        }
+
+        fn render(&self, almanac: &Self::Value) -> String {
+            const CATEGORIES: [&str; 8] = [
+                "seed",
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                "location",
+            ];
+
+            let seeds = almanac
+                .seeds
+                .iter()
+                .map(|(start, length)| format!("{start} {length}"))
+                .join(" ");
+            let mut input = format!("seeds: {seeds}\n");
+
+            for (i, map) in almanac.maps.iter().enumerate() {
+                input.push_str(&format!(
+                    "\n{}-to-{} map:\n",
+                    CATEGORIES[i],
+                    CATEGORIES[i + 1]
+                ));
+                for (target, source, length) in map {
+                    input.push_str(&format!("{target} {source} {length}\n"));
+                }
+            }
+
+            input
+        }
+
+        fn shrink(&self, almanac: &Self::Value) -> Vec<Self::Value> {
+            let seeds = shrink_vec(&almanac.seeds, 1, |&(start, length)| {
+                let starts = shrink_number(start, 0).into_iter().map(|s| (s, length));
+                let lengths = shrink_number(length, 1).into_iter().map(|l| (start, l));
+                starts.chain(lengths).collect()
+            })
+            .into_iter()
+            .map(|seeds| Almanac {
+                seeds,
+                maps: almanac.maps.clone(),
+            });
+
+            let maps = shrink_vec(&almanac.maps, 1, |map| {
+                shrink_vec(map, 1, |&(target, source, length)| {
+                    let targets = shrink_number(target, 0)
+                        .into_iter()
+                        .map(|t| (t, source, length));
+                    let sources = shrink_number(source, 0)
+                        .into_iter()
+                        .map(|s| (target, s, length));
+                    let lengths = shrink_number(length, 1)
+                        .into_iter()
+                        .map(|l| (target, source, l));
+                    targets.chain(sources).chain(lengths).collect()
+                })
+            })
+            .into_iter()
+            .map(|maps| Almanac {
+                seeds: almanac.seeds.clone(),
+                maps,
+            });
+
+            seeds.chain(maps).filter(Almanac::is_valid).collect()
+        }
+    }
+
+    /// Maps a seed through every map by scanning all entries.
+    fn map_by_scanning(maps: &[Vec<Entry>], seed: usize) -> usize {
+        maps.iter().fold(seed, |seed, map| {
+            map.iter()
+                .find(|entry| entry.includes(seed))
+                .map_or(seed, |entry| entry.target_start + seed - entry.source_start)
+        })
+    }
+
+    fn reference_part_one(input: &str) -> Option<u32> {
+        let lines = input.lines().map(|l| l.to_string()).collect_vec();
+        let maps = parse_entries(&lines[2..].to_vec());
+
+        parse_seeds(&lines[0])
+            .into_iter()
+            .map(|seed| map_by_scanning(&maps, seed) as u32)
+            .min()
+    }
+
+    fn reference_part_two(input: &str) -> Option<u32> {
+        let lines = input.lines().map(|l| l.to_string()).collect_vec();
+        let maps = parse_entries(&lines[2..].to_vec());
+
+        parse_seed_ranges(&lines[0])
+            .iter()
+            .flat_map(|range| range.start..range.start + range.length)
+            .map(|seed| map_by_scanning(&maps, seed) as u32)
+            .min()
+    }
+
+    #[test]
+    fn test_seed_ranges_match_single_seeds() {
+        check(&Almanacs, 500, part_one, reference_part_one);
+        check(&Almanacs, 500, part_two, reference_part_two);
+    }
 }
@@ -1,21 +1,9 @@
-fn ways_to_win(time: u64, record_distance: u64) -> u64 {
-    let mut ways_to_win = 0;
-
-    for hold_time in 0..time {
-        let travel_time = time - hold_time;
-        let distance_travelled = hold_time * travel_time;
-        if distance_travelled > record_distance {
-            ways_to_win += 1;
-        }
-    }
-
-    ways_to_win
-}
+use advent_of_code::{differential::Rng, math::count_products_above as ways_to_win};
 
 pub fn part_one(input: &str) -> Option<u64> {
     let times = input
         .lines()
-        .nth(0)
+        .next()
         .expect("should have first line")
         .split("Time: ")
         .nth(1)
@@ -49,7 +37,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     let times = input
         .lines()
-        .nth(0)
+        .next()
         .expect("should have first line")
         .split("Time: ")
         .nth(1)
@@ -84,11 +72,21 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(ways_to_win(times, distances))
 }
 
-advent_of_code::main!(6);
+/// One race of `n` milliseconds with a record near the best possible distance, for `cargo scale 6`.
+/// Part two reads the same race.
+fn generate(n: usize, rng: &mut Rng) -> String {
+    let time = n as u64;
+    let best = time / 2 * (time - time / 2);
+    let record = best - rng.below(best / 2 + 1);
+    format!("Time: {time}\nDistance: {record}\n")
+}
+
+advent_of_code::main!(6, scale);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{check, shrink_number, shrink_vec, Generator, Rng};
 
     #[test]
     fn test_part_one() {
@@ -101,4 +99,90 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", 6));
         assert_eq!(result, Some(71503));
     }
+
+    /// Up to `max_races` races as `(time, record distance)`, with records around the best possible
+    /// distance. Part two joins the numbers, so fewer races keep its reference fast.
+    struct Races {
+        max_races: u64,
+    }
+
+    impl Generator for Races {
+        type Value = Vec<(u64, u64)>;
+
+        fn generate(&self, rng: &mut Rng) -> Self::Value {
+            (0..rng.between(1, self.max_races))
+                .map(|_| {
+                    let time = rng.between(1, 40);
+                    (time, rng.below(time * time / 4 + 2))
+                })
+                .collect()
+        }
+
+        fn render(&self, races: &Self::Value) -> String {
+            let row =
+                |values: Vec<u64>| values.iter().map(|v| format!("{v:>4}")).collect::<String>();
+
+            format!(
+                "Time:    {}\nDistance:{}\n",
+                row(races.iter().map(|r| r.0).collect()),
+                row(races.iter().map(|r| r.1).collect())
+            )
+        }
+
+        fn shrink(&self, races: &Self::Value) -> Vec<Self::Value> {
+            shrink_vec(races, 1, |&(time, record)| {
+                let times = shrink_number(time, 1).into_iter().map(|t| (t, record));
+                let records = shrink_number(record, 0).into_iter().map(|r| (time, r));
+                times.chain(records).collect()
+            })
+        }
+    }
+
+    /// Tries every hold time.
+    fn count_wins_by_loop(time: u64, record: u64) -> u64 {
+        (0..=time)
+            .filter(|hold| hold * (time - hold) > record)
+            .count() as u64
+    }
+
+    fn reference_part_one(input: &str) -> Option<u64> {
+        let rows = input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .skip(1)
+                    .map(|n| n.parse().unwrap())
+                    .collect::<Vec<u64>>()
+            })
+            .collect::<Vec<_>>();
+
+        Some(
+            rows[0]
+                .iter()
+                .zip(&rows[1])
+                .map(|(&time, &record)| count_wins_by_loop(time, record))
+                .product(),
+        )
+    }
+
+    fn reference_part_two(input: &str) -> Option<u64> {
+        let rows = input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .skip(1)
+                    .collect::<String>()
+                    .parse()
+                    .unwrap()
+            })
+            .collect::<Vec<u64>>();
+
+        Some(count_wins_by_loop(rows[0], rows[1]))
+    }
+
+    #[test]
+    fn test_closed_form_matches_loop() {
+        check(&Races { max_races: 4 }, 300, part_one, reference_part_one);
+        check(&Races { max_races: 3 }, 100, part_two, reference_part_two);
+    }
 }
@@ -1,30 +1,25 @@
-use std::{collections::HashMap, fmt::Display};
-
-fn card_strength(card: char) -> u32 {
-    match card {
-        'A' => 14,
-        'K' => 13,
-        'Q' => 12,
-        'J' => 11,
-        'T' => 10,
-        '2'..='9' => card.to_digit(10).expect("Invalid card"),
-        _ => 0, // Invalid card
-    }
-}
+use std::fmt::Display;
 
-fn card_strength_with_joker(card: char) -> u32 {
-    match card {
-        'A' => 14,
-        'K' => 13,
-        'Q' => 12,
-        'T' => 10,
-        '2'..='9' => card.to_digit(10).expect("Invalid card"),
-        'J' => 1, // J is now the weakest
-        _ => 0,   // Invalid card
-    }
+use advent_of_code::differential::Rng;
+
+/// How hands are compared: the card labels from weakest to strongest, and the labels that act as wildcards.
+struct Rules {
+    order: &'static str,
+    wildcards: &'static str,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+const STANDARD_RULES: Rules = Rules {
+    order: "23456789TJQKA",
+    wildcards: "",
+};
+
+/// J cards are jokers: they are the weakest individual cards, but pretend to be whatever makes the best hand.
+const JOKER_RULES: Rules = Rules {
+    order: "J23456789TQKA",
+    wildcards: "J",
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum HandType {
     HighCard,
     OnePair,
@@ -35,29 +30,9 @@ enum HandType {
     FiveOfAKind,
 }
 
-#[derive(Debug)]
-struct Hand {
-    cards: Vec<u32>,
-    bid: u32,
-}
-
-impl Display for Hand {
+impl Display for HandType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let cards = self
-            .cards
-            .iter()
-            .map(|&card| match card {
-                14 => 'A',
-                13 => 'K',
-                12 => 'Q',
-                11 | 1 => 'J',
-                10 => 'T',
-                2..=9 => card.to_string().chars().next().unwrap(),
-                _ => '?',
-            })
-            .collect::<String>();
-
-        let card_type = match self.hand_type_with_jokers() {
+        let name = match self {
             HandType::HighCard => "High Card",
             HandType::OnePair => "One Pair",
             HandType::TwoPair => "Two Pair",
@@ -67,214 +42,137 @@ impl Display for Hand {
             HandType::FiveOfAKind => "Five of a Kind",
         };
 
-        write!(f, "{} ({})", cards, card_type)
+        f.pad(name)
     }
 }
 
-impl Hand {
-    const JOKER_VALUE: u32 = 1;
-
-    fn hand_type(&self) -> HandType {
-        match self
-            .card_frequencies()
-            .values()
-            .collect::<Vec<&u32>>()
-            .as_slice()
-        {
-            [5] => HandType::FiveOfAKind,
-            [4, 1] | [1, 4] => HandType::FourOfAKind,
-            [3, 2] | [2, 3] => HandType::FullHouse,
-            [3, 1, 1] | [1, 3, 1] | [1, 1, 3] => HandType::ThreeOfAKind,
-            [2, 2, 1] | [2, 1, 2] | [1, 2, 2] => HandType::TwoPair,
-            [2, 1, 1, 1] | [1, 2, 1, 1] | [1, 1, 2, 1] | [1, 1, 1, 2] => HandType::OnePair,
-            _ => HandType::HighCard,
-        }
-    }
-
-    fn hand_type_with_jokers(&self) -> HandType {
-        // Count jokers
-        let joker_count = self
-            .cards
-            .iter()
-            .filter(|&&card| card == Hand::JOKER_VALUE)
-            .count() as u32;
-
-        // If there are jokers, we need to determine the best possible hand
-        if joker_count > 0 {
-            return self.best_hand_with_jokers(joker_count);
-        }
-
-        // If no jokers, use the original method
-        self.hand_type()
+impl Rules {
+    fn strength(&self, card: char) -> usize {
+        self.order.find(card).expect("Invalid card")
     }
 
-    fn best_hand_with_jokers(&self, joker_count: u32) -> HandType {
-        let mut frequencies = self.card_frequencies();
-
-        // Remove the frequency of Jokers for analysis
-        frequencies.remove(&Hand::JOKER_VALUE);
-
-        // Start from the strongest hand and check if it can be formed with available jokers
-        if joker_count == 5 {
-            return HandType::FiveOfAKind; // All jokers can form any hand
-        }
-
-        // Check for Five of a Kind
-        if let Some(&max_freq) = frequencies.values().max() {
-            if max_freq + joker_count >= 5 {
-                return HandType::FiveOfAKind;
+    /// Classifies a hand by its frequency signature, i.e. how often each label occurs, sorted descending.
+    /// Wildcards always do best by joining the most frequent label.
+    fn classify(&self, cards: &str) -> HandType {
+        let mut counts = [0_u8; 13];
+        let mut wildcards = 0;
+
+        for card in cards.chars() {
+            if self.wildcards.contains(card) {
+                wildcards += 1;
+            } else {
+                counts[self.strength(card)] += 1;
             }
         }
 
-        if let Some(&max_freq) = frequencies.values().max() {
-            if max_freq == 4 || (max_freq == 3 && joker_count >= 2) {
-                return HandType::FiveOfAKind;
-            }
-        }
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts[0] += wildcards;
 
-        // Check for Four of a Kind or Full House
-        if let Some(&max_freq) = frequencies.values().max() {
-            if max_freq + joker_count >= 4 {
-                return HandType::FourOfAKind;
-            }
-            if max_freq == 3 && joker_count + frequencies.len() as u32 - 1 >= 2 {
-                return HandType::FullHouse;
-            }
-        }
-
-        // Check for Three of a Kind
-        if joker_count >= 3 || (frequencies.values().any(|&v| v == 2) && joker_count >= 1) {
-            return HandType::ThreeOfAKind;
-        }
-
-        // Check for Two Pair
-        if frequencies.len() as u32 + joker_count >= 4 {
-            return HandType::TwoPair;
-        }
-
-        // Check for One Pair
-        if joker_count >= 1 || frequencies.values().any(|&v| v == 2) {
-            return HandType::OnePair;
-        }
-
-        HandType::HighCard
-    }
-
-    fn card_frequencies(&self) -> HashMap<u32, u32> {
-        let mut frequencies = HashMap::new();
-
-        for value in &self.cards {
-            *frequencies.entry(*value).or_insert(0) += 1;
-        }
-
-        frequencies
-    }
-
-    fn compare_with(&self, other: &Hand) -> std::cmp::Ordering {
-        let is_equal_hand_type = self.hand_type() == other.hand_type();
-
-        if !is_equal_hand_type {
-            return self.hand_type().cmp(&other.hand_type());
-        }
-
-        // If two hands have the same type, a second ordering rule takes effect. Start by comparing the first card in each hand. If these cards are different, the hand with the stronger first card is considered stronger. If the first card in each hand have the same label, however, then move on to considering the second card in each hand. If they differ, the hand with the higher second card wins; otherwise, continue with the third card in each hand, then the fourth, then the fifth.
-        for (card_a, card_b) in self.cards.iter().zip(other.cards.iter()) {
-            if card_a != card_b {
-                return card_a.cmp(card_b);
-            }
+        match counts {
+            [5, ..] => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2, ..] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
         }
-
-        std::cmp::Ordering::Equal
     }
+}
 
-    fn compare_with_jokers(&self, other: &Hand) -> std::cmp::Ordering {
-        let self_type = self.hand_type_with_jokers();
-        let other_type = other.hand_type_with_jokers();
+#[derive(Debug)]
+struct Hand<'a> {
+    cards: &'a str,
+    bid: u32,
+    hand_type: HandType,
+    strengths: Vec<usize>,
+}
 
-        if self_type != other_type {
-            return self_type.cmp(&other_type);
-        }
+impl<'a> Hand<'a> {
+    fn parse(line: &'a str, rules: &Rules) -> Self {
+        let (cards, bid) = line.split_once(' ').expect("Invalid hand");
 
-        // In case of a tie, compare based on actual card values, considering 'J' as the weakest
-        for (card_a, card_b) in self.cards.iter().zip(other.cards.iter()) {
-            if card_a != card_b {
-                return card_a.cmp(card_b);
-            }
+        Hand {
+            cards,
+            bid: bid.trim().parse().expect("Invalid bid"),
+            hand_type: rules.classify(cards),
+            strengths: cards.chars().map(|card| rules.strength(card)).collect(),
         }
-
-        std::cmp::Ordering::Equal
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// Parses all hands and sorts them from weakest to strongest. A hand's rank is its index plus one.
+/// Hands of the same type are compared card by card, from the first card to the last.
+fn rank_hands<'a>(input: &'a str, rules: &Rules) -> Vec<Hand<'a>> {
     let mut hands = input
         .lines()
-        .map(|line| line.split_whitespace())
-        .map(|mut line| (line.next().unwrap(), line.next().unwrap()))
-        .map(|(hand, bid)| {
-            (
-                hand.chars().collect::<Vec<char>>(),
-                bid.parse::<u32>().unwrap(),
-            )
-        })
-        .map(|(hand, bid)| Hand {
-            cards: hand.iter().map(|card| card_strength(*card)).collect(),
-            bid,
-        })
+        .map(|line| Hand::parse(line, rules))
         .collect::<Vec<Hand>>();
 
-    hands.sort_by(|a, b| a.compare_with(b));
+    hands.sort_by(|a, b| {
+        a.hand_type
+            .cmp(&b.hand_type)
+            .then_with(|| a.strengths.cmp(&b.strengths))
+    });
 
-    let total_winnings = hands
+    hands
+}
+
+fn total_winnings(input: &str, rules: &Rules) -> u32 {
+    rank_hands(input, rules)
         .iter()
-        .enumerate()
-        .map(|(rank, hand)| hand.bid * (rank as u32 + 1))
-        .sum::<u32>();
+        .zip(1..)
+        .map(|(hand, rank)| hand.bid * rank)
+        .sum()
+}
 
-    Some(total_winnings)
+/// Lists every hand with its type, rank and winnings.
+fn explain_ranking(input: &str, rules: &Rules) {
+    for (hand, rank) in rank_hands(input, rules).iter().zip(1..) {
+        println!(
+            "{rank:>5}  {}  {:<15}  {:>5} × {rank:<5} = {}",
+            hand.cards,
+            hand.hand_type,
+            hand.bid,
+            hand.bid * rank
+        );
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut hands = input
-        .lines()
-        .map(|line| line.split_whitespace())
-        .map(|mut line| (line.next().unwrap(), line.next().unwrap()))
-        .map(|(hand, bid)| {
-            (
-                hand.chars().collect::<Vec<char>>(),
-                bid.parse::<u32>().unwrap(),
-            )
-        })
-        .map(|(hand, bid)| Hand {
-            cards: hand
-                .iter()
-                .map(|card| card_strength_with_joker(*card))
-                .collect(),
-            bid,
-        })
-        .collect::<Vec<Hand>>();
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(total_winnings(input, &STANDARD_RULES))
+}
 
-    hands.sort_by(|a, b| a.compare_with_jokers(b));
+pub fn part_two(input: &str) -> Option<u32> {
+    Some(total_winnings(input, &JOKER_RULES))
+}
 
-    for hand in hands.iter() {
-        println!("{}", hand);
-    }
+/// Prints how each hand was ranked, with `cargo solve 7 --explain`.
+fn explain(input: &str) {
+    println!("\nPart 1 ranking:");
+    explain_ranking(input, &STANDARD_RULES);
+    println!("\nPart 2 ranking:");
+    explain_ranking(input, &JOKER_RULES);
+}
 
-    let total_winnings = hands
-        .iter()
-        .enumerate()
-        .map(|(rank, hand)| hand.bid * (rank as u32 + 1))
-        .sum::<u32>();
+/// `n` random hands, for `cargo scale 7`.
+fn generate(n: usize, rng: &mut Rng) -> String {
+    let labels = STANDARD_RULES.order.chars().collect::<Vec<char>>();
 
-    Some(total_winnings)
-    // None
+    (0..n)
+        .map(|_| {
+            let cards = (0..5).map(|_| *rng.pick(&labels)).collect::<String>();
+            format!("{cards} {}\n", rng.between(1, 1000))
+        })
+        .collect()
 }
 
-advent_of_code::main!(7);
+advent_of_code::main!(7, explain, scale);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{check, shrink_number, shrink_vec, Generator, Rng};
 
     #[test]
     fn test_part_one() {
@@ -293,4 +191,119 @@ mod tests {
         let result = part_two("JKKK2 10\nQQQQ2 20\n");
         assert_eq!(result, Some(10 * 1 + 20 * 2));
     }
+
+    /// Best type reachable by replacing each joker with any other label, by trying all of them.
+    fn brute_force_joker_type(cards: &str, labels: &[char]) -> HandType {
+        match cards.find('J') {
+            None => STANDARD_RULES.classify(cards),
+            Some(i) => labels
+                .iter()
+                .map(|&label| {
+                    let replaced = format!("{}{label}{}", &cards[..i], &cards[i + 1..]);
+                    brute_force_joker_type(&replaced, labels)
+                })
+                .max()
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_joker_classification_matches_brute_force() {
+        let labels = ['2', '3', '4', '5', 'A'];
+        let alphabet = ['J', '2', '3', '4', '5', 'A'];
+
+        for i in 0..alphabet.len().pow(5) {
+            let cards = (0..5)
+                .map(|position| alphabet[i / alphabet.len().pow(position) % alphabet.len()])
+                .collect::<String>();
+
+            assert_eq!(
+                JOKER_RULES.classify(&cards),
+                brute_force_joker_type(&cards, &labels),
+                "{cards}"
+            );
+        }
+    }
+
+    /// Hands as `(cards, bid)`. Jokers are drawn more often than other labels, so hands with several
+    /// of them are common.
+    struct Hands;
+
+    impl Generator for Hands {
+        type Value = Vec<(String, u64)>;
+
+        fn generate(&self, rng: &mut Rng) -> Self::Value {
+            let labels = STANDARD_RULES.order.chars().collect::<Vec<char>>();
+
+            (0..rng.between(1, 30))
+                .map(|_| {
+                    let cards = (0..5)
+                        .map(|_| {
+                            if rng.chance(1, 4) {
+                                'J'
+                            } else {
+                                *rng.pick(&labels)
+                            }
+                        })
+                        .collect();
+                    (cards, rng.between(1, 1000))
+                })
+                .collect()
+        }
+
+        fn render(&self, hands: &Self::Value) -> String {
+            hands
+                .iter()
+                .map(|(cards, bid)| format!("{cards} {bid}\n"))
+                .collect()
+        }
+
+        fn shrink(&self, hands: &Self::Value) -> Vec<Self::Value> {
+            shrink_vec(hands, 1, |(cards, bid)| {
+                // Turn one card into a 2, the weakest label that is not a joker.
+                let simpler_cards = (0..cards.len())
+                    .filter(|&i| &cards[i..=i] != "2")
+                    .map(|i| (format!("{}2{}", &cards[..i], &cards[i + 1..]), *bid));
+                let smaller_bids = shrink_number(*bid, 1)
+                    .into_iter()
+                    .map(|b| (cards.clone(), b));
+                simpler_cards.chain(smaller_bids).collect()
+            })
+        }
+    }
+
+    /// Ranks hands by trying every replacement for each joker. Replacing a joker with a label that
+    /// is not in the hand always gives the same type, so one such label stands in for all of them.
+    fn reference_part_two(input: &str) -> Option<u32> {
+        let mut hands = input
+            .lines()
+            .map(|line| {
+                let (cards, bid) = line.split_once(' ').unwrap();
+                let mut labels = cards.chars().filter(|&c| c != 'J').collect::<Vec<char>>();
+                labels.extend("23456".chars().find(|c| !cards.contains(*c)));
+
+                let hand_type = brute_force_joker_type(cards, &labels);
+                let strengths = cards
+                    .chars()
+                    .map(|c| JOKER_RULES.strength(c))
+                    .collect::<Vec<usize>>();
+                (hand_type, strengths, bid.parse::<u32>().unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        hands.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+        Some(
+            hands
+                .iter()
+                .zip(1..)
+                .map(|(hand, rank)| hand.2 * rank)
+                .sum(),
+        )
+    }
+
+    #[test]
+    fn test_jokers_match_enumeration() {
+        check(&Hands, 300, part_two, reference_part_two);
+    }
 }
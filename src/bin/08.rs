@@ -1,10 +1,14 @@
-use num::integer::lcm as lowest_common_multiple;
-use std::collections::HashMap;
+use advent_of_code::{
+    graph::{Graph, GraphBuilder, NodeId},
+    math::crt_pair,
+    template::params::Params,
+};
 
+/// Defaults for the `start` and `end` parameters.
 const START_LABEL: &str = "AAA";
 const END_LABEL: &str = "ZZZ";
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Direction {
     Left,
     Right,
@@ -23,84 +27,182 @@ impl Direction {
     }
 }
 
-type Label = String;
-type LeftRight = (Label, Label);
-
-fn create_graph(input: &str) -> HashMap<Label, LeftRight> {
-    input
-        .lines()
-        .skip(2)
-        .map(|line| {
-            let parts = line.split(" = ").collect::<Vec<&str>>();
-            let label = parts[0].to_string();
-            let (left_label, right_label) = parts[1]
-                .trim_matches(|c| c == '(' || c == ')')
-                .split_once(", ")
-                .unwrap();
-            (label, (left_label.to_string(), right_label.to_string()))
-        })
-        .collect()
+/// Builds the network so that every node has its left neighbour first and its right neighbour second.
+fn create_graph(input: &str) -> Graph<'_> {
+    let mut builder = GraphBuilder::new();
+
+    for line in input.lines().skip(2) {
+        let (label, neighbours) = line
+            .split_once(" = ")
+            .expect("should have format `AAA = (BBB, CCC)`");
+        let (left_label, right_label) = neighbours
+            .trim_matches(|c| c == '(' || c == ')')
+            .split_once(", ")
+            .unwrap();
+        builder.edge(label, left_label).edge(label, right_label);
+    }
+
+    builder.build()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+fn step(graph: &Graph, node: NodeId, direction: Direction) -> NodeId {
+    let neighbours = graph.neighbours(node);
+    match direction {
+        Direction::Left => neighbours[0],
+        Direction::Right => neighbours[1],
+    }
+}
+
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let directions =
-        Direction::parse_directions(input.lines().nth(0).expect("should have first line"));
+        Direction::parse_directions(input.lines().next().expect("should have first line"));
 
     let graph = create_graph(input);
 
-    let mut current_label = START_LABEL.to_string();
+    let mut current = graph.id(&params.get_or("start", START_LABEL.to_string()))?;
+    let end = graph.id(&params.get_or("end", END_LABEL.to_string()))?;
     let mut steps: u32 = 0;
 
-    while current_label != END_LABEL {
-        let direction = &directions[steps as usize % directions.len()];
-
-        current_label = match direction {
-            Direction::Left => graph.get(&current_label).unwrap().0.clone(),
-            Direction::Right => graph.get(&current_label).unwrap().1.clone(),
-        };
-
+    while current != end {
+        let direction = directions[steps as usize % directions.len()];
+        current = step(&graph, current, direction);
         steps += 1;
     }
 
     Some(steps)
 }
 
-fn part_two(input: &str) -> Option<u32> {
-    let directions =
-        Direction::parse_directions(input.lines().nth(0).expect("should have first line"));
+/// The path of one ghost, described by the (node, instruction index) state it walks through.
+/// After `tail` steps, the ghost enters a loop of `length` steps that it repeats forever.
+#[derive(Debug)]
+struct GhostCycle {
+    tail: u64,
+    length: u64,
+    /// Steps before the loop at which the ghost stands on an end node. These happen only once.
+    tail_hits: Vec<u64>,
+    /// Steps in `tail..tail + length` at which the ghost stands on an end node. These repeat every `length` steps.
+    cycle_hits: Vec<u64>,
+}
 
-    let graph = create_graph(input);
+impl GhostCycle {
+    fn analyse(
+        graph: &Graph,
+        directions: &[Direction],
+        is_end_node: &[bool],
+        start: NodeId,
+    ) -> Self {
+        // First step at which each (node, instruction index) state was seen.
+        let mut first_seen = vec![u64::MAX; graph.len() * directions.len()];
+        let mut end_hits = vec![];
+        let mut current = start;
+        let mut steps: u64 = 0;
 
-    // Collect all starting nodes (nodes ending with 'A')
-    let start_nodes = graph
-        .keys()
-        .filter(|&k| k.ends_with('A'))
-        .collect::<Vec<&String>>();
+        loop {
+            let instruction = (steps % directions.len() as u64) as usize;
+            let state = current as usize * directions.len() + instruction;
 
-    // Calculate the LCM of steps for each starting node
-    let result_lcm = start_nodes
-        .iter()
-        .map(|&start_node| {
-            let mut current_label = start_node;
-            let mut steps: usize = 0;
-
-            while !current_label.ends_with('Z') {
-                let direction = &directions[steps % directions.len()];
-                current_label = match direction {
-                    Direction::Left => &graph.get(current_label).unwrap().0,
-                    Direction::Right => &graph.get(current_label).unwrap().1,
+            if first_seen[state] != u64::MAX {
+                let tail = first_seen[state];
+                let (tail_hits, cycle_hits) = end_hits.iter().partition(|&&hit| hit < tail);
+
+                return GhostCycle {
+                    tail,
+                    length: steps - tail,
+                    tail_hits,
+                    cycle_hits,
                 };
-                steps += 1;
             }
 
-            steps as u64
+            first_seen[state] = steps;
+
+            if is_end_node[current as usize] {
+                end_hits.push(steps);
+            }
+
+            current = step(graph, current, directions[instruction]);
+            steps += 1;
+        }
+    }
+
+    fn is_at_end(&self, steps: u64) -> bool {
+        if steps < self.tail {
+            return self.tail_hits.contains(&steps);
+        }
+
+        let offset = (steps - self.tail) % self.length;
+        self.cycle_hits.contains(&(self.tail + offset))
+    }
+}
+
+/// Finds the first step at which every ghost stands on an end node.
+fn first_common_hit(ghosts: &[GhostCycle]) -> Option<u64> {
+    let latest = ghosts.iter().max_by_key(|ghost| ghost.tail)?;
+
+    // Before `latest` enters its loop, only its tail hits are candidates.
+    let early_hit = latest
+        .tail_hits
+        .iter()
+        .copied()
+        .find(|&steps| ghosts.iter().all(|ghost| ghost.is_at_end(steps)));
+
+    if early_hit.is_some() {
+        return early_hit;
+    }
+
+    // From then on, every ghost is inside its loop: solve the congruences for each combination of hits.
+    let mut congruences: Vec<(u64, u64)> = vec![(0, 1)];
+
+    for ghost in ghosts {
+        congruences = congruences
+            .iter()
+            .flat_map(|&congruence| {
+                ghost
+                    .cycle_hits
+                    .iter()
+                    .filter_map(move |&hit| crt_pair(congruence, (hit, ghost.length)))
+            })
+            .collect();
+        congruences.sort_unstable();
+        congruences.dedup();
+    }
+
+    congruences
+        .into_iter()
+        .filter_map(|(a, m)| {
+            if a >= latest.tail {
+                Some(a)
+            } else {
+                (latest.tail - a)
+                    .div_ceil(m)
+                    .checked_mul(m)
+                    .and_then(|offset| a.checked_add(offset))
+            }
         })
-        .fold(1, lowest_common_multiple);
+        .min()
+}
+
+pub fn part_two(input: &str, _params: &Params) -> Option<u64> {
+    let directions =
+        Direction::parse_directions(input.lines().next().expect("should have first line"));
+
+    let graph = create_graph(input);
 
-    Some(result_lcm as u32)
+    let is_end_node = graph
+        .nodes()
+        .map(|node| graph.label(node).ends_with('Z'))
+        .collect::<Vec<bool>>();
+
+    // Collect all starting nodes (nodes ending with 'A') and analyse the path each ghost takes
+    let ghosts = graph
+        .nodes()
+        .filter(|&node| graph.label(node).ends_with('A'))
+        .map(|start| GhostCycle::analyse(&graph, &directions, &is_end_node, start))
+        .collect::<Vec<GhostCycle>>();
+
+    first_common_hit(&ghosts)
 }
 
-advent_of_code::main!(8);
+advent_of_code::main!(8, params);
 
 #[cfg(test)]
 mod tests {
@@ -118,6 +220,7 @@ DDD = (DDD, DDD)
 EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)",
+            &Params::default(),
         );
         assert_eq!(result, Some(2));
     }
@@ -130,10 +233,24 @@ ZZZ = (ZZZ, ZZZ)",
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)",
+            &Params::default(),
         );
         assert_eq!(result, Some(6));
     }
 
+    #[test]
+    fn test_part_one_with_params() {
+        let result = part_one(
+            "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)",
+            &Params::default().with("start", "BBB"),
+        );
+        assert_eq!(result, Some(3));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(
@@ -147,7 +264,58 @@ ZZZ = (ZZZ, ZZZ)",
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)",
+            &Params::default(),
         );
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_part_two_offset_hits() {
+        // The ghosts first reach an end node after 1 and 2 steps, but their loops are 4 and 3 steps long.
+        let result = part_two(
+            "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11C, 11C)
+11C = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22A, 22A)",
+            &Params::default(),
+        );
+        assert_eq!(result, Some(5));
+    }
+
+    #[test]
+    fn test_part_two_tail_hit() {
+        // The first ghost only ever passes its end node once, before getting stuck.
+        let result = part_two(
+            "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22A, 22A)",
+            &Params::default(),
+        );
+        assert_eq!(result, Some(1));
+    }
+
+    #[test]
+    fn test_part_two_never_aligned() {
+        let result = part_two(
+            "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22C, 22C)
+22C = (22A, 22A)",
+            &Params::default(),
+        );
+        assert_eq!(result, None);
+    }
 }
@@ -1,94 +1,32 @@
-struct Sequence {
-    values: Vec<i32>,
-}
-
-impl Sequence {
-    fn is_all_zeroes(&self) -> bool {
-        self.values.iter().all(|&x| x == 0)
-    }
-
-    fn add_next(&mut self) {
-        if self.is_all_zeroes() {
-            self.values.push(0);
-            return;
-        }
-
-        let mut sub_sequence = Sequence {
-            values: self
-                .values
-                .windows(2)
-                .map(|window| window[1] - window[0])
-                .collect(),
-        };
-        sub_sequence.add_next();
-
-        let result = self.values.last().unwrap() + sub_sequence.values.last().unwrap_or(&0);
-        self.values.push(result);
-    }
-
-    fn add_previous(&mut self) {
-        if self.is_all_zeroes() {
-            self.values.insert(0, 0);
-            return;
-        }
-
-        let mut sub_sequence = Sequence {
-            values: self
-                .values
-                .windows(2)
-                .map(|window| window[1] - window[0])
-                .collect(),
-        };
-        sub_sequence.add_previous();
-
-        let result = self.values[0] - sub_sequence.values[0];
-        self.values.insert(0, result);
-    }
-}
-
-pub fn part_one(input: &str) -> Option<i32> {
-    let histories = input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|digit| digit.trim().parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
+use advent_of_code::{parse::ints_per_line, polynomial::DifferenceTable, template::params::Params};
+
+/// Sums the value each history predicts `steps` positions after its last value,
+/// or before its first value if `steps` is negative.
+fn sum_of_predictions(input: &str, steps: isize) -> Option<i64> {
+    let sum = ints_per_line::<i64>(input)
+        .map(|history| {
+            let table = DifferenceTable::new(&history.collect::<Vec<i64>>())?;
+            if steps >= 0 {
+                table.forward(steps.unsigned_abs())
+            } else {
+                table.backward(steps.unsigned_abs())
+            }
         })
-        .collect::<Vec<Vec<i32>>>();
-
-    let mut sum = 0;
-
-    for history in histories {
-        let mut sequence = Sequence { values: history };
-        sequence.add_next();
-        sum += sequence.values.last().unwrap_or(&0);
-    }
+        .try_fold(0_i128, |sum, prediction| sum.checked_add(prediction?))?;
 
-    Some(sum)
+    i64::try_from(sum).ok()
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let histories = input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|digit| digit.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .collect::<Vec<Vec<i32>>>();
-
-    let mut sum = 0;
-
-    for history in histories {
-        let mut sequence = Sequence { values: history };
-        sequence.add_previous();
-        sum += sequence.values[0];
-    }
+/// With `--param steps=<n>`, part one predicts `n` values ahead and part two `n` values back.
+pub fn part_one(input: &str, params: &Params) -> Option<i64> {
+    sum_of_predictions(input, params.get_or("steps", 1))
+}
 
-    Some(sum)
+pub fn part_two(input: &str, params: &Params) -> Option<i64> {
+    sum_of_predictions(input, -params.get_or("steps", 1))
 }
 
-advent_of_code::main!(9);
+advent_of_code::main!(9, params);
 
 #[cfg(test)]
 mod tests {
@@ -96,13 +34,27 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 9));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", 9),
+            &Params::default(),
+        );
         assert_eq!(result, Some(114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 9));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", 9),
+            &Params::default(),
+        );
         assert_eq!(result, Some(2));
     }
+
+    #[test]
+    fn test_more_steps() {
+        let input = "0 3 6 9 12 15\n1 3 6 10 15 21";
+        let params = Params::default().with("steps", 3);
+        assert_eq!(part_one(input, &params), Some(24 + 45));
+        assert_eq!(part_two(input, &params), Some(-9 + 1));
+    }
 }
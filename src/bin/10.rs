@@ -1,5 +1,9 @@
 use core::panic;
 
+use advent_of_code::{
+    geometry::{Location, Point, Polygon},
+    template::viz::{box_drawing, Color, Frame, Style, Visualizer},
+};
 use pathfinding::matrix::Matrix;
 
 /// A position on the grid, with a row and column.
@@ -69,17 +73,6 @@ fn cycle(start: Position, grid: &Matrix<u8>) -> impl Iterator<Item = Position> +
     }))
 }
 
-// Parses barriers from the grid based on the cycle starting at `start`
-fn parse_barriers(start: Position, grid: &Matrix<u8>) -> Matrix<u8> {
-    let mut barriers = Matrix::new(grid.rows, grid.columns, b'.');
-    for pos in cycle(start, &grid) {
-        // Mark the cycle path on the barriers matrix
-        barriers[pos] = grid[pos];
-    }
-
-    barriers
-}
-
 pub fn part_one(input: &str) -> Option<u32> {
     let (start, grid) = parse(input);
     Some((cycle(start, &grid).count() / 2) as u32)
@@ -87,21 +80,67 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let (start, grid) = parse(input);
-    let mut inside = false;
-
-    let enclosed_tiles = parse_barriers(start, &grid)
-        .items()
-        .filter(|&((_, c), &i)| {
-            inside &= c != 0;
-            inside ^= matches!(i, b'|' | b'J' | b'L');
-            inside && i == b'.'
-        })
-        .count();
+    let tiles = cycle(start, &grid).collect::<Vec<Position>>();
+    Some(loop_polygon(&tiles).interior_points() as u32)
+}
+
+/// The polygon enclosed by the loop. Every tile of the loop is one of its vertices.
+fn loop_polygon(tiles: &[Position]) -> Polygon {
+    Polygon::new(
+        tiles
+            .iter()
+            .map(|&(row, col)| (row as i64, col as i64))
+            .collect::<Vec<Point>>(),
+    )
+}
+
+/// Traces the loop, then highlights the enclosed tiles.
+fn visualize(input: &str, viz: &mut Visualizer) {
+    let (start, grid) = parse(input);
+    let tiles = cycle(start, &grid).collect::<Vec<Position>>();
+    // About 50 frames for tracing, however long the loop is.
+    let frame_every = (tiles.len() / 50).max(1);
+
+    let pipes = input.chars().map(box_drawing).collect::<String>();
+    let mut frame = Frame::from_text(&pipes).styled(Style::fg(Color::Gray));
+
+    let mut on_loop = Matrix::new(grid.rows, grid.columns, false);
+
+    for (i, &(row, col)) in tiles.iter().enumerate() {
+        on_loop[(row, col)] = true;
+        // The grid is padded by one tile on each side.
+        let symbol = box_drawing(grid[(row, col)] as char);
+        frame.set((row - 1, col - 1), symbol, Style::fg(Color::Yellow).bold());
+
+        if i % frame_every == 0 {
+            frame.set_caption(format!(
+                "Tracing the loop: {} of {} tiles",
+                i + 1,
+                tiles.len()
+            ));
+            viz.emit(&frame);
+        }
+    }
+
+    // The same polygon as in part two, so the highlighted tiles add up to its answer.
+    let polygon = loop_polygon(&tiles);
+    let mut enclosed = 0;
+    for (row, col) in grid.keys().filter(|&tile| !on_loop[tile]) {
+        if polygon.locate((row as i64, col as i64)) == Location::Inside {
+            enclosed += 1;
+            frame.set((row - 1, col - 1), '█', Style::fg(Color::Green));
+        }
+    }
 
-    Some(enclosed_tiles as u32)
+    frame.set_caption(format!(
+        "The loop has {} tiles, its farthest point is {} steps away, and it encloses {enclosed} tiles",
+        tiles.len(),
+        tiles.len() / 2
+    ));
+    viz.emit(&frame);
 }
 
-advent_of_code::main!(10);
+advent_of_code::main!(10, viz);
 
 #[cfg(test)]
 mod tests {
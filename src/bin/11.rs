@@ -1,138 +1,294 @@
-struct Position {
-    row: usize,
-    col: usize,
-}
-
-type Grid = Vec<Vec<u8>>;
+use advent_of_code::{
+    differential::Rng,
+    geometry::expanded_distance_sum,
+    template::{
+        params::Params,
+        viz::{Color, Frame, Style, Visualizer},
+    },
+};
 
-fn parse_input(input: &str) -> (Grid, Vec<Position>) {
-    let mut galaxies = vec![];
-
-    let space = input
+/// Positions of all galaxies as `(row, col)`.
+fn parse_galaxies(input: &str) -> Vec<(usize, usize)> {
+    input
         .lines()
         .enumerate()
-        .map(|(row, line)| {
+        .flat_map(|(row, line)| {
             line.bytes()
                 .enumerate()
-                // Determine the position of the galaxies
-                .map(|(col, b)| {
-                    if b == b'#' {
-                        galaxies.push(Position { row, col });
-                        1
-                    } else {
-                        0
-                    }
-                })
-                .collect::<Vec<u8>>()
+                .filter(|&(_, b)| b == b'#')
+                .map(move |(col, _)| (row, col))
         })
-        .collect::<Grid>();
+        .collect()
+}
+
+/// Sum of the shortest distances between all pairs of galaxies,
+/// after every empty row and column has grown to `expansion` rows or columns.
+pub fn sum_of_distances(input: &str, expansion: u64) -> Option<u64> {
+    Some(expanded_distance_sum(&parse_galaxies(input), expansion))
+}
+
+/// Both parts take the expansion from `--param expansion=<n>` if it is set.
+pub fn part_one(input: &str, params: &Params) -> Option<u64> {
+    sum_of_distances(input, params.get_or("expansion", 2))
+}
 
-    (space, galaxies)
+pub fn part_two(input: &str, params: &Params) -> Option<u64> {
+    sum_of_distances(input, params.get_or("expansion", 1_000_000))
 }
 
-fn get_empty_rows_cols(space: &[Vec<u8>]) -> (Vec<usize>, Vec<usize>) {
-    let mut empty_rows = vec![];
-    let mut empty_cols = vec![];
-    let (rows, cols) = (space.len(), space[0].len());
+/// Highlights the empty rows and columns, then shows the universe after they expanded.
+/// Always uses part one's expansion of 2, since `--param expansion` of part two would not fit on
+/// any screen.
+fn visualize(input: &str, _params: &Params, viz: &mut Visualizer) {
+    const EXPANSION: usize = 2;
 
-    for row in 0..rows {
-        if space[row].iter().all(|&c| c == 0) {
-            empty_rows.push(row);
-        }
+    let galaxies = parse_galaxies(input);
+    let original = Frame::from_text(input);
+
+    let mut is_galaxy = vec![vec![false; original.cols()]; original.rows()];
+    let mut empty_row = vec![true; original.rows()];
+    let mut empty_col = vec![true; original.cols()];
+    for &(row, col) in &galaxies {
+        is_galaxy[row][col] = true;
+        empty_row[row] = false;
+        empty_col[col] = false;
     }
 
-    for col in 0..cols {
-        if (0..rows).all(|r| space[r][col] == 0) {
-            empty_cols.push(col);
+    let galaxy = Style::fg(Color::Yellow).bold();
+    let empty = Style::fg(Color::Gray).on(Color::Blue);
+    let style_of = |(row, col): (usize, usize)| {
+        if is_galaxy[row][col] {
+            galaxy
+        } else if empty_row[row] || empty_col[col] {
+            empty
+        } else {
+            Style::fg(Color::Gray)
         }
-    }
+    };
 
-    (empty_rows, empty_cols)
-}
+    let mut frame = original.clone();
+    for row in 0..frame.rows() {
+        for col in 0..frame.cols() {
+            frame.set_style((row, col), style_of((row, col)));
+        }
+    }
+    frame.set_caption(format!(
+        "{} galaxies, {} empty rows and {} empty columns",
+        galaxies.len(),
+        empty_row.iter().filter(|&&empty| empty).count(),
+        empty_col.iter().filter(|&&empty| empty).count()
+    ));
+    viz.emit(&frame);
 
-/// Calculate the distance between all galaxies
-/// The distance between two galaxies is the sum of the distance between their rows and columns
-/// plus the number of empty rows and columns between them times the expansion factor
-fn calculate_distance(
-    galaxies: &[Position],
-    empty_rows: &[usize],
-    empty_cols: &[usize],
-    expansion: usize,
-) -> usize {
-    galaxies
-        .iter()
-        .enumerate()
-        .map(
-            |(
-                id,
-                &Position {
-                    row: row_a,
-                    col: col_a,
-                },
-            )| {
-                galaxies
-                    .iter()
-                    .skip(id + 1)
-                    .map(
-                        |&Position {
-                             row: row_b,
-                             col: col_b,
-                         }| {
-                            let mut distance = row_a.abs_diff(row_b) + col_a.abs_diff(col_b);
-
-                            distance += (row_a + 1..row_b)
-                                .filter(|&row_a| empty_rows.contains(&row_a))
-                                .count()
-                                * (expansion - 1);
-
-                            distance += (col_a.min(col_b) + 1..col_a.max(col_b))
-                                .filter(|&col_a| empty_cols.contains(&col_a))
-                                .count()
-                                * (expansion - 1);
-
-                            distance
-                        },
-                    )
-                    .sum::<usize>()
-            },
-        )
-        .sum()
-}
+    // Each original row or column maps to `EXPANSION` copies if it is empty, or to itself otherwise.
+    let widths = |empty: &[bool]| {
+        empty
+            .iter()
+            .map(|&empty| if empty { EXPANSION } else { 1 })
+            .collect::<Vec<usize>>()
+    };
+    let row_widths = widths(&empty_row);
+    let col_widths = widths(&empty_col);
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (space, galaxies) = parse_input(input);
-    let (empty_rows, empty_cols) = get_empty_rows_cols(&space);
+    let mut expanded = Frame::new(row_widths.iter().sum(), col_widths.iter().sum());
+    let mut target_row = 0;
+    for (row, &row_width) in row_widths.iter().enumerate() {
+        for r in target_row..target_row + row_width {
+            let mut target_col = 0;
+            for (col, &col_width) in col_widths.iter().enumerate() {
+                let symbol = original.get((row, col)).map_or('.', |cell| cell.symbol);
+                for c in target_col..target_col + col_width {
+                    expanded.set((r, c), symbol, style_of((row, col)));
+                }
+                target_col += col_width;
+            }
+        }
+        target_row += row_width;
+    }
 
-    Some(calculate_distance(&galaxies, &empty_rows, &empty_cols, 2))
+    let sum = expanded_distance_sum(&galaxies, EXPANSION as u64);
+    expanded.set_caption(format!(
+        "After expanding by {EXPANSION}, the distances between galaxies sum up to {sum}"
+    ));
+    viz.emit(&expanded);
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (space, galaxies) = parse_input(input);
-    let (empty_rows, empty_cols) = get_empty_rows_cols(&space);
+/// A square image with `n` galaxies, about one in twenty tiles, for `cargo scale 11`.
+fn generate(n: usize, rng: &mut Rng) -> String {
+    let side = ((n * 20) as f64).sqrt().ceil() as usize;
+    let mut tiles = vec![b'.'; side * side];
 
-    Some(calculate_distance(
-        &galaxies,
-        &empty_rows,
-        &empty_cols,
-        1_000_000,
-    ))
+    let mut placed = 0;
+    while placed < n {
+        let i = rng.below(tiles.len() as u64) as usize;
+        if tiles[i] == b'.' {
+            tiles[i] = b'#';
+            placed += 1;
+        }
+    }
+
+    tiles
+        .chunks(side)
+        .map(|row| format!("{}\n", String::from_utf8_lossy(row)))
+        .collect()
 }
 
-advent_of_code::main!(11);
+advent_of_code::main!(11, params, viz, scale);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{check, shrink_vec, Generator, Rng};
+    use std::collections::VecDeque;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 11));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", 11),
+            &Params::default(),
+        );
         assert_eq!(result, Some(374));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 11));
-        assert_eq!(result, None);
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", 11),
+            &Params::default(),
+        );
+        assert_eq!(result, Some(82_000_210));
+    }
+
+    #[test]
+    fn test_smaller_expansions() {
+        let input = advent_of_code::template::read_file("examples", 11);
+        let result = part_two(&input, &Params::default().with("expansion", 10));
+        assert_eq!(result, Some(1030));
+        let result = part_two(&input, &Params::default().with("expansion", 100));
+        assert_eq!(result, Some(8410));
+    }
+
+    /// Small images of the universe, as rows of `true` for galaxies.
+    struct Universes;
+
+    impl Generator for Universes {
+        type Value = Vec<Vec<bool>>;
+
+        fn generate(&self, rng: &mut Rng) -> Self::Value {
+            let cols = rng.between(1, 8);
+            (0..rng.between(1, 8))
+                .map(|_| (0..cols).map(|_| rng.chance(1, 5)).collect())
+                .collect()
+        }
+
+        fn render(&self, image: &Self::Value) -> String {
+            image
+                .iter()
+                .map(|row| {
+                    let mut line = row
+                        .iter()
+                        .map(|&galaxy| if galaxy { '#' } else { '.' })
+                        .collect::<String>();
+                    line.push('\n');
+                    line
+                })
+                .collect()
+        }
+
+        fn shrink(&self, image: &Self::Value) -> Vec<Self::Value> {
+            let cols = image[0].len();
+
+            let without_rows = shrink_vec(image, 1, |_| vec![]);
+            let without_cols = (0..cols).filter(|_| cols > 1).map(|col| {
+                image
+                    .iter()
+                    .map(|row| [&row[..col], &row[col + 1..]].concat())
+                    .collect()
+            });
+            let without_galaxies = (0..image.len() * cols)
+                .filter(|&i| image[i / cols][i % cols])
+                .map(|i| {
+                    let mut smaller = image.clone();
+                    smaller[i / cols][i % cols] = false;
+                    smaller
+                });
+
+            without_rows
+                .into_iter()
+                .chain(without_cols)
+                .chain(without_galaxies)
+                .collect()
+        }
+    }
+
+    /// Draws the expanded universe and walks from every galaxy to all others.
+    fn sum_of_distances_by_bfs(input: &str, expansion: usize) -> Option<u64> {
+        let image = input
+            .lines()
+            .map(|line| line.bytes().map(|b| b == b'#').collect::<Vec<bool>>())
+            .collect::<Vec<_>>();
+        let cols = image[0].len();
+
+        let empty_col = (0..cols)
+            .map(|col| image.iter().all(|row| !row[col]))
+            .collect::<Vec<bool>>();
+        let mut grid = vec![];
+        for row in &image {
+            let expanded = row
+                .iter()
+                .zip(&empty_col)
+                .flat_map(|(&galaxy, &empty)| vec![galaxy; if empty { expansion } else { 1 }])
+                .collect::<Vec<bool>>();
+            let copies = if row.contains(&true) { 1 } else { expansion };
+            grid.extend(vec![expanded; copies]);
+        }
+
+        let (height, width) = (grid.len(), grid.first().map_or(0, Vec::len));
+        let galaxies = (0..height * width)
+            .filter(|&i| grid[i / width][i % width])
+            .collect::<Vec<usize>>();
+
+        let mut sum = 0;
+        for &start in &galaxies {
+            let mut distances = vec![u64::MAX; height * width];
+            let mut queue = VecDeque::from([start]);
+            distances[start] = 0;
+
+            while let Some(i) = queue.pop_front() {
+                let (row, col) = (i / width, i % width);
+                let neighbours = [
+                    (row > 0).then(|| i - width),
+                    (row + 1 < height).then(|| i + width),
+                    (col > 0).then(|| i - 1),
+                    (col + 1 < width).then(|| i + 1),
+                ];
+                for next in neighbours.into_iter().flatten() {
+                    if distances[next] == u64::MAX {
+                        distances[next] = distances[i] + 1;
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            sum += galaxies
+                .iter()
+                .filter(|&&other| other > start)
+                .map(|&other| distances[other])
+                .sum::<u64>();
+        }
+
+        Some(sum)
+    }
+
+    #[test]
+    fn test_formula_matches_bfs() {
+        for expansion in 1..=4 {
+            check(
+                &Universes,
+                100,
+                |input| sum_of_distances(input, expansion as u64),
+                |input| sum_of_distances_by_bfs(input, expansion),
+            );
+        }
     }
 }
@@ -0,0 +1,248 @@
+/// Differential testing: runs a fast implementation and a simple reference implementation on random
+/// puzzle inputs and fails with the smallest input they disagree on.
+///
+/// Each day describes its input format with a [`Generator`], which produces random values, renders
+/// them as puzzle input and proposes smaller variants for shrinking:
+///
+/// ```ignore
+/// check(&Races, 200, |input| part_one(input), |input| reference_part_one(input));
+/// ```
+use std::fmt::Debug;
+
+/// Small xorshift generator, so runs are reproducible without extra dependencies.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Creates a generator. The seed must not be zero.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "xorshift needs a non-zero seed");
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// A value in `low..=high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    /// True with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// A random element of `items`.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// Random values of a puzzle input format.
+pub trait Generator {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// The value as puzzle input.
+    fn render(&self, value: &Self::Value) -> String;
+
+    /// Smaller variants of `value`, tried in order when shrinking a failing input. All of them must
+    /// be valid inputs. Without an implementation, failing inputs are reported as generated.
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        vec![]
+    }
+}
+
+/// Seed of the inputs [`check`] generates. Fixed, so failures are reproducible.
+pub const SEED: u64 = 0x2023_1225;
+
+/// Runs `fast` and `reference` on `cases` generated inputs.
+///
+/// # Panics
+/// Panics with the smallest input found by shrinking if the implementations disagree.
+pub fn check<G, T>(
+    generator: &G,
+    cases: usize,
+    fast: impl Fn(&str) -> T,
+    reference: impl Fn(&str) -> T,
+) where
+    G: Generator,
+    T: PartialEq + Debug,
+{
+    let mut rng = Rng::new(SEED);
+    let disagree = |value: &G::Value| {
+        let input = generator.render(value);
+        fast(&input) != reference(&input)
+    };
+
+    for case in 0..cases {
+        let value = generator.generate(&mut rng);
+
+        if !disagree(&value) {
+            continue;
+        }
+
+        let (value, steps) = shrink(generator, value, disagree);
+        let input = generator.render(&value);
+
+        panic!(
+            "fast and reference implementations disagree on case {case} (shrunk in {steps} steps):\n\
+             {input}\n\
+             fast: {:?}\n\
+             reference: {:?}",
+            fast(&input),
+            reference(&input),
+            input = input.trim_end()
+        );
+    }
+}
+
+/// Greedily replaces `value` with the first smaller variant that still fails, until none does.
+fn shrink<G: Generator>(
+    generator: &G,
+    mut value: G::Value,
+    fails: impl Fn(&G::Value) -> bool,
+) -> (G::Value, usize) {
+    let mut steps = 0;
+
+    while let Some(smaller) = generator.shrink(&value).into_iter().find(|v| fails(v)) {
+        value = smaller;
+        steps += 1;
+    }
+
+    (value, steps)
+}
+
+/// Smaller variants of a list: without its second half, without each single item, then with each
+/// item shrunk by `shrink_item`. Lists never get shorter than `min_len`.
+pub fn shrink_vec<T: Clone>(
+    items: &[T],
+    min_len: usize,
+    shrink_item: impl Fn(&T) -> Vec<T>,
+) -> Vec<Vec<T>> {
+    let mut candidates = vec![];
+
+    if items.len() / 2 >= min_len.max(1) {
+        candidates.push(items[..items.len() / 2].to_vec());
+    }
+
+    if items.len() > min_len {
+        for i in 0..items.len() {
+            let mut smaller = items.to_vec();
+            smaller.remove(i);
+            candidates.push(smaller);
+        }
+    }
+
+    for (i, item) in items.iter().enumerate() {
+        for shrunk in shrink_item(item) {
+            let mut smaller = items.to_vec();
+            smaller[i] = shrunk;
+            candidates.push(smaller);
+        }
+    }
+
+    candidates
+}
+
+/// Smaller numbers down to `min`: `min` itself, halfway there, and one less.
+#[must_use]
+pub fn shrink_number(n: u64, min: u64) -> Vec<u64> {
+    if n <= min {
+        return vec![];
+    }
+
+    let mut candidates = vec![min, min + (n - min) / 2, n - 1];
+    candidates.retain(|&c| c >= min && c < n);
+    candidates.dedup();
+    candidates
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, shrink_number, shrink_vec, Generator, Rng};
+
+    /// Lists of small numbers, one per line.
+    struct Numbers;
+
+    impl Generator for Numbers {
+        type Value = Vec<u64>;
+
+        fn generate(&self, rng: &mut Rng) -> Self::Value {
+            (0..rng.between(1, 20)).map(|_| rng.below(100)).collect()
+        }
+
+        fn render(&self, value: &Self::Value) -> String {
+            value.iter().map(|n| format!("{n}\n")).collect()
+        }
+
+        fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+            shrink_vec(value, 1, |&n| shrink_number(n, 0))
+        }
+    }
+
+    fn sum(input: &str) -> u64 {
+        input.lines().map(|l| l.parse::<u64>().unwrap()).sum()
+    }
+
+    #[test]
+    fn passes_when_implementations_agree() {
+        check(&Numbers, 200, sum, |input| {
+            input
+                .lines()
+                .fold(0, |acc, l| acc + l.parse::<u64>().unwrap())
+        });
+    }
+
+    #[test]
+    fn shrinks_to_minimal_failing_input() {
+        // Wrong as soon as any number is 42 or more.
+        let buggy = |input: &str| -> u64 {
+            input
+                .lines()
+                .map(|l| l.parse::<u64>().unwrap())
+                .map(|n| n.min(41))
+                .sum()
+        };
+
+        let message = std::panic::catch_unwind(|| check(&Numbers, 200, buggy, sum))
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap();
+
+        assert!(
+            message.contains(":\n42\nfast: 41\nreference: 42"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn shrinks_numbers_and_lists() {
+        assert_eq!(shrink_number(10, 0), vec![0, 5, 9]);
+        assert_eq!(shrink_number(1, 0), vec![0]);
+        assert_eq!(shrink_number(3, 3), Vec::<u64>::new());
+
+        let candidates = shrink_vec(&[4_u64, 2], 1, |&n| shrink_number(n, 3));
+        assert_eq!(candidates, vec![vec![4], vec![2], vec![4], vec![3, 2]]);
+    }
+
+    #[test]
+    fn generates_in_range() {
+        let mut rng = Rng::new(7);
+        assert!((0..1000).all(|_| (3..=5).contains(&rng.between(3, 5))));
+        assert!((0..1000).all(|_| rng.below(10) < 10));
+        assert!((0..1000).all(|_| !rng.chance(0, 3)));
+    }
+}
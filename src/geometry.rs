@@ -0,0 +1,241 @@
+/// Integer geometry for polygons on a lattice: area via the shoelace formula, lattice point counts via
+/// Pick's theorem, and point-in-polygon tests.
+use crate::math::gcd;
+
+/// A lattice point. The axes can mean anything, e.g. `(row, column)`.
+pub type Point = (i64, i64);
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// A closed polygon given by its vertices in order. The last vertex connects back to the first.
+/// The polygon is assumed to be simple, i.e. its edges do not cross.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    /// Creates a polygon from its vertices. Points in the middle of an edge are allowed, so every
+    /// tile of a traced loop can be passed in.
+    #[must_use]
+    pub fn new(vertices: Vec<Point>) -> Self {
+        Self { vertices }
+    }
+
+    /// Creates a polygon by walking from `start`, taking `length` steps in `direction` for each item.
+    /// This is the "dig plan" format, e.g. `((0, 1), 6)` for six steps to the right.
+    pub fn from_steps(start: Point, steps: impl IntoIterator<Item = (Point, i64)>) -> Self {
+        let mut position = start;
+        let mut vertices = vec![start];
+
+        for ((dx, dy), length) in steps {
+            position = (position.0 + dx * length, position.1 + dy * length);
+            vertices.push(position);
+        }
+
+        // The walk ends where it started, which is already the first vertex.
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+
+        Self { vertices }
+    }
+
+    #[must_use]
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+
+    /// Twice the enclosed area, via the shoelace formula. Doubling keeps the result an integer.
+    #[must_use]
+    pub fn twice_area(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| a.0 * b.1 - a.1 * b.0)
+            .sum::<i64>()
+            .abs()
+    }
+
+    /// Number of lattice points on the edges.
+    #[must_use]
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| gcd(b.0 - a.0, b.1 - a.1).unwrap())
+            .sum()
+    }
+
+    /// Number of lattice points strictly inside, via Pick's theorem: `A = I + B / 2 - 1`.
+    #[must_use]
+    pub fn interior_points(&self) -> i64 {
+        (self.twice_area() - self.boundary_points() + 2) / 2
+    }
+
+    /// Number of lattice points inside or on the boundary, e.g. the tiles of a dug out lagoon.
+    #[must_use]
+    pub fn enclosed_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Locates `point` using the crossing number, with exact integer arithmetic.
+    #[must_use]
+    pub fn locate(&self, point: Point) -> Location {
+        let (px, py) = (i128::from(point.0), i128::from(point.1));
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            let (ax, ay) = (i128::from(a.0), i128::from(a.1));
+            let (bx, by) = (i128::from(b.0), i128::from(b.1));
+
+            let cross = (bx - ax) * (py - ay) - (by - ay) * (px - ax);
+            let within_box =
+                px >= ax.min(bx) && px <= ax.max(bx) && py >= ay.min(by) && py <= ay.max(by);
+
+            if cross == 0 && within_box {
+                return Location::Boundary;
+            }
+
+            // Count edges crossed by a ray from `point` towards increasing x.
+            if (ay > py) != (by > py) {
+                let crosses = if by > ay { cross > 0 } else { cross < 0 };
+                inside ^= crosses;
+            }
+        }
+
+        if inside {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+}
+
+/// Sum of the Manhattan distances between all pairs of `points`, where every row or column that
+/// contains no point counts as `expansion` rows or columns.
+/// Runs in `O(n log n)`: the axes are independent, so each is sorted and summed in one pass.
+#[must_use]
+pub fn expanded_distance_sum(points: &[(usize, usize)], expansion: u64) -> u64 {
+    let rows = points.iter().map(|p| p.0).collect::<Vec<usize>>();
+    let cols = points.iter().map(|p| p.1).collect::<Vec<usize>>();
+    expanded_axis_distance_sum(rows, expansion) + expanded_axis_distance_sum(cols, expansion)
+}
+
+/// Sum of `|a - b|` over all pairs of coordinates on one axis, see [`expanded_distance_sum`].
+#[must_use]
+pub fn expanded_axis_distance_sum(mut coordinates: Vec<usize>, expansion: u64) -> u64 {
+    coordinates.sort_unstable();
+
+    let mut sum = 0;
+    // Running sum of the expanded coordinates seen so far.
+    let mut prefix = 0;
+    let mut expanded = 0;
+    let mut previous = coordinates.first().copied().unwrap_or(0);
+
+    for (count, &coordinate) in (0_u64..).zip(&coordinates) {
+        if coordinate > previous {
+            // Occupied coordinates advance by 1, the empty ones in between by `expansion`.
+            let gap = (coordinate - previous) as u64;
+            expanded += 1 + (gap - 1) * expansion;
+            previous = coordinate;
+        }
+
+        sum += expanded * count - prefix;
+        prefix += expanded;
+    }
+
+    sum
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{expanded_distance_sum, Location, Polygon};
+
+    fn get_mock_polygon() -> Polygon {
+        // A 3x4 rectangle with a 1x2 notch cut out of one corner.
+        Polygon::new(vec![(0, 0), (0, 4), (2, 4), (2, 2), (3, 2), (3, 0)])
+    }
+
+    #[test]
+    fn computes_area() {
+        assert_eq!(get_mock_polygon().twice_area(), 20);
+        assert_eq!(Polygon::new(vec![(0, 0), (3, 0), (0, 4)]).twice_area(), 12);
+        assert_eq!(Polygon::new(vec![(0, 0), (0, 4), (3, 0)]).twice_area(), 12);
+    }
+
+    #[test]
+    fn counts_lattice_points() {
+        let polygon = get_mock_polygon();
+        assert_eq!(polygon.boundary_points(), 14);
+        assert_eq!(polygon.interior_points(), 4);
+        assert_eq!(polygon.enclosed_points(), 18);
+    }
+
+    #[test]
+    fn builds_from_steps() {
+        let (right, down, left, up) = ((0, 1), (1, 0), (0, -1), (-1, 0));
+        let polygon = Polygon::from_steps(
+            (0, 0),
+            [
+                (right, 6),
+                (down, 5),
+                (left, 2),
+                (down, 2),
+                (right, 2),
+                (down, 2),
+                (left, 5),
+                (up, 2),
+                (left, 1),
+                (up, 2),
+                (right, 2),
+                (up, 3),
+                (left, 2),
+                (up, 2),
+            ],
+        );
+        assert_eq!(polygon.vertices().len(), 14);
+        assert_eq!(polygon.enclosed_points(), 62);
+    }
+
+    #[test]
+    fn locates_points() {
+        let polygon = get_mock_polygon();
+        assert_eq!(polygon.locate((1, 1)), Location::Inside);
+        assert_eq!(polygon.locate((1, 3)), Location::Inside);
+        assert_eq!(polygon.locate((0, 2)), Location::Boundary);
+        assert_eq!(polygon.locate((2, 3)), Location::Boundary);
+        assert_eq!(polygon.locate((3, 3)), Location::Outside);
+        assert_eq!(polygon.locate((-1, 1)), Location::Outside);
+        assert_eq!(polygon.locate((1, 5)), Location::Outside);
+    }
+
+    #[test]
+    fn agrees_with_pick() {
+        let polygon = get_mock_polygon();
+        let inside = (-1..5)
+            .flat_map(|x| (-1..6).map(move |y| (x, y)))
+            .filter(|&p| polygon.locate(p) == Location::Inside)
+            .count();
+        assert_eq!(inside as i64, polygon.interior_points());
+    }
+
+    #[test]
+    fn sums_expanded_distances() {
+        let points = [(0, 0), (0, 3), (4, 3)];
+        assert_eq!(expanded_distance_sum(&points, 1), 3 + 7 + 4);
+        // Rows 1..=3 and columns 1..=2 are empty.
+        assert_eq!(expanded_distance_sum(&points, 10), 21 + 52 + 31);
+        assert_eq!(expanded_distance_sum(&[(5, 5)], 10), 0);
+        assert_eq!(expanded_distance_sum(&[], 10), 0);
+    }
+}
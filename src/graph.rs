@@ -0,0 +1,299 @@
+/// Directed graphs over string labels.
+/// Labels are interned into dense integer ids when the graph is built, adjacency is stored in a
+/// compressed (CSR) layout, and traversals delegate to the `pathfinding` crate.
+use pathfinding::prelude::{astar, bfs, bfs_reach, dfs, dijkstra, strongly_connected_components};
+use std::collections::HashMap;
+
+/// Dense id of a node, in the range `0..graph.len()`.
+pub type NodeId = u32;
+
+/// Maps string labels to dense ids and back, borrowing the labels from the input.
+#[derive(Debug, Default, Clone)]
+pub struct Interner<'a> {
+    ids: HashMap<&'a str, NodeId>,
+    labels: Vec<&'a str>,
+}
+
+impl<'a> Interner<'a> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of `label`, assigning the next free id if it has not been seen yet.
+    pub fn intern(&mut self, label: &'a str) -> NodeId {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+
+        let id = NodeId::try_from(self.labels.len()).expect("too many labels to intern");
+        self.ids.insert(label, id);
+        self.labels.push(label);
+        id
+    }
+
+    #[must_use]
+    pub fn get(&self, label: &str) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    #[must_use]
+    pub fn label(&self, id: NodeId) -> &'a str {
+        self.labels[id as usize]
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Iterates over all `(id, label)` pairs in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &'a str)> + '_ {
+        (0..).zip(self.labels.iter().copied())
+    }
+}
+
+/// Collects labelled edges before freezing them into a [`Graph`].
+#[derive(Debug, Default)]
+pub struct GraphBuilder<'a> {
+    labels: Interner<'a>,
+    edges: Vec<(NodeId, NodeId, u64)>,
+}
+
+impl<'a> GraphBuilder<'a> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node without edges and returns its id.
+    pub fn node(&mut self, label: &'a str) -> NodeId {
+        self.labels.intern(label)
+    }
+
+    /// Adds an edge of weight 1.
+    pub fn edge(&mut self, from: &'a str, to: &'a str) -> &mut Self {
+        self.weighted_edge(from, to, 1)
+    }
+
+    pub fn weighted_edge(&mut self, from: &'a str, to: &'a str, weight: u64) -> &mut Self {
+        let from = self.labels.intern(from);
+        let to = self.labels.intern(to);
+        self.edges.push((from, to, weight));
+        self
+    }
+
+    /// Freezes the graph. The outgoing edges of each node keep the order they were added in.
+    #[must_use]
+    pub fn build(self) -> Graph<'a> {
+        let node_count = self.labels.len();
+        let mut offsets = vec![0; node_count + 1];
+
+        for &(from, _, _) in &self.edges {
+            offsets[from as usize + 1] += 1;
+        }
+
+        for i in 0..node_count {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut next = offsets.clone();
+        let mut targets = vec![0; self.edges.len()];
+        let mut weights = vec![0; self.edges.len()];
+
+        for (from, to, weight) in self.edges {
+            let slot = &mut next[from as usize];
+            targets[*slot] = to;
+            weights[*slot] = weight;
+            *slot += 1;
+        }
+
+        Graph {
+            labels: self.labels,
+            offsets,
+            targets,
+            weights,
+        }
+    }
+}
+
+/// Directed, weighted graph with interned labels, see [`GraphBuilder`].
+#[derive(Debug, Clone)]
+pub struct Graph<'a> {
+    labels: Interner<'a>,
+    offsets: Vec<usize>,
+    targets: Vec<NodeId>,
+    weights: Vec<u64>,
+}
+
+impl<'a> Graph<'a> {
+    #[must_use]
+    pub fn id(&self, label: &str) -> Option<NodeId> {
+        self.labels.get(label)
+    }
+
+    #[must_use]
+    pub fn label(&self, id: NodeId) -> &'a str {
+        self.labels.label(id)
+    }
+
+    #[must_use]
+    pub fn labels(&self) -> &Interner<'a> {
+        &self.labels
+    }
+
+    /// Number of nodes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..NodeId::try_from(self.len()).unwrap()
+    }
+
+    /// Outgoing neighbours of `id`, in insertion order.
+    #[must_use]
+    pub fn neighbours(&self, id: NodeId) -> &[NodeId] {
+        &self.targets[self.edge_range(id)]
+    }
+
+    /// Outgoing `(neighbour, weight)` pairs of `id`, in insertion order.
+    pub fn edges(&self, id: NodeId) -> impl Iterator<Item = (NodeId, u64)> + '_ {
+        let range = self.edge_range(id);
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.weights[range].iter().copied())
+    }
+
+    fn edge_range(&self, id: NodeId) -> std::ops::Range<usize> {
+        self.offsets[id as usize]..self.offsets[id as usize + 1]
+    }
+
+    /// Shortest path by number of edges from `start` to the first node matching `success`.
+    pub fn bfs(&self, start: NodeId, success: impl FnMut(&NodeId) -> bool) -> Option<Vec<NodeId>> {
+        bfs(&start, |&n| self.neighbours(n).iter().copied(), success)
+    }
+
+    /// Some path from `start` to a node matching `success`, found depth-first.
+    pub fn dfs(&self, start: NodeId, success: impl FnMut(&NodeId) -> bool) -> Option<Vec<NodeId>> {
+        dfs(start, |&n| self.neighbours(n).iter().copied(), success)
+    }
+
+    /// Cheapest path and its total weight from `start` to a node matching `success`.
+    pub fn dijkstra(
+        &self,
+        start: NodeId,
+        success: impl FnMut(&NodeId) -> bool,
+    ) -> Option<(Vec<NodeId>, u64)> {
+        dijkstra(&start, |&n| self.edges(n), success)
+    }
+
+    /// Like [`Graph::dijkstra`], guided by an admissible `heuristic`.
+    pub fn astar(
+        &self,
+        start: NodeId,
+        heuristic: impl FnMut(&NodeId) -> u64,
+        success: impl FnMut(&NodeId) -> bool,
+    ) -> Option<(Vec<NodeId>, u64)> {
+        astar(&start, |&n| self.edges(n), heuristic, success)
+    }
+
+    /// All nodes reachable from `start` (including itself), in breadth-first order.
+    #[must_use]
+    pub fn reachable(&self, start: NodeId) -> Vec<NodeId> {
+        bfs_reach(start, |&n| self.neighbours(n).iter().copied()).collect()
+    }
+
+    /// Partitions the graph into its strongly connected components.
+    #[must_use]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        let nodes = self.nodes().collect::<Vec<NodeId>>();
+        strongly_connected_components(&nodes, |&n| self.neighbours(n).iter().copied())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Graph, GraphBuilder};
+
+    fn get_mock_graph() -> Graph<'static> {
+        let mut builder = GraphBuilder::new();
+        builder
+            .weighted_edge("a", "b", 7)
+            .weighted_edge("a", "c", 1)
+            .weighted_edge("c", "b", 2)
+            .weighted_edge("b", "d", 1)
+            .weighted_edge("d", "b", 1);
+        builder.node("e");
+        builder.build()
+    }
+
+    #[test]
+    fn interns_labels() {
+        let graph = get_mock_graph();
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.id("c"), Some(2));
+        assert_eq!(graph.label(3), "d");
+        assert_eq!(graph.id("x"), None);
+    }
+
+    #[test]
+    fn keeps_edge_order() {
+        let graph = get_mock_graph();
+        let a = graph.id("a").unwrap();
+        let neighbours: Vec<&str> = graph
+            .neighbours(a)
+            .iter()
+            .map(|&n| graph.label(n))
+            .collect();
+        assert_eq!(neighbours, vec!["b", "c"]);
+        assert!(graph.neighbours(graph.id("e").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn finds_paths() {
+        let graph = get_mock_graph();
+        let (a, d) = (graph.id("a").unwrap(), graph.id("d").unwrap());
+
+        assert_eq!(graph.bfs(a, |&n| n == d).unwrap().len(), 3);
+        assert!(graph.dfs(a, |&n| n == d).is_some());
+        assert_eq!(graph.dijkstra(a, |&n| n == d).unwrap().1, 4);
+        assert_eq!(graph.astar(a, |_| 0, |&n| n == d).unwrap().1, 4);
+        assert_eq!(graph.bfs(d, |&n| n == a), None);
+    }
+
+    #[test]
+    fn finds_reachable_nodes() {
+        let graph = get_mock_graph();
+        let mut reachable = graph.reachable(graph.id("c").unwrap());
+        reachable.sort_unstable();
+        assert_eq!(reachable, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn finds_strongly_connected_components() {
+        let graph = get_mock_graph();
+        let mut components = graph
+            .strongly_connected_components()
+            .into_iter()
+            .map(|mut c| {
+                c.sort_unstable();
+                c
+            })
+            .collect::<Vec<_>>();
+        components.sort();
+        assert_eq!(components, vec![vec![0], vec![1, 3], vec![2], vec![4]]);
+    }
+}
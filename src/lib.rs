@@ -1 +1,8 @@
+pub mod differential;
+pub mod geometry;
+pub mod graph;
+pub mod math;
+pub mod memoize;
+pub mod parse;
+pub mod polynomial;
 pub mod template;
@@ -1,12 +1,18 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, encrypt, migrate, read, scaffold, scale, session, solve, today,
+};
 use args::{parse, AppArguments};
 
 mod args {
+    use advent_of_code::template::{
+        commands::download::Days, config::Config, submission::SubmitOptions, viz::VizOptions,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: u8,
+            days: Days,
+            force: bool,
         },
         Read {
             day: u8,
@@ -14,28 +20,75 @@ mod args {
         Scaffold {
             day: u8,
         },
+        Scale {
+            day: u8,
+            year: Option<u16>,
+            params: Vec<String>,
+        },
+        Session {
+            token: Option<String>,
+            status: bool,
+            force: bool,
+        },
         Solve {
             day: u8,
+            year: Option<u16>,
             release: bool,
             time: bool,
-            submit: Option<u8>,
+            explain: bool,
+            viz: Option<VizOptions>,
+            submit: Option<SubmitOptions>,
+            params: Vec<String>,
         },
         All {
+            year: Option<u16>,
             release: bool,
             time: bool,
         },
+        Migrate {
+            dry_run: bool,
+        },
+        Encrypt,
+        Today {
+            watch: bool,
+        },
+    }
+
+    /// `on` turns a flag on and `off` turns it off, otherwise the default from the config applies.
+    fn flag(
+        args: &mut pico_args::Arguments,
+        on: &'static str,
+        off: &'static str,
+        default: bool,
+    ) -> bool {
+        let enabled = args.contains(on);
+        let disabled = args.contains(off);
+        (default || enabled) && !disabled
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let defaults = &Config::get().defaults;
+        // every command accepts `--year`, which the config already applied for this process.
+        let year: Option<u16> = args.opt_value_from_str("--year")?;
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                year,
+                release: flag(&mut args, "--release", "--no-release", defaults.release),
+                time: flag(&mut args, "--time", "--no-time", defaults.time),
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                force: args.contains("--force"),
+                days: if args.contains("--all") {
+                    Days::All
+                } else {
+                    args.free_from_str()?
+                },
+            },
+            Some("encrypt") => AppArguments::Encrypt,
+            Some("migrate") => AppArguments::Migrate {
+                dry_run: args.contains("--dry-run"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
@@ -43,11 +96,28 @@ mod args {
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
             },
+            Some("scale") => AppArguments::Scale {
+                day: args.free_from_str()?,
+                year,
+                params: args.values_from_str("--param")?,
+            },
+            Some("session") => AppArguments::Session {
+                status: args.contains("--status"),
+                force: args.contains("--force"),
+                token: args.opt_free_from_str()?,
+            },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                year,
+                release: flag(&mut args, "--release", "--no-release", defaults.release),
+                submit: SubmitOptions::from_arguments(&mut args)?,
+                time: flag(&mut args, "--time", "--no-time", defaults.time),
+                explain: args.contains("--explain"),
+                viz: VizOptions::from_arguments(&mut args)?,
+                params: args.values_from_str("--param")?,
+            },
+            Some("today") => AppArguments::Today {
+                watch: !args.contains("--no-watch"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -75,16 +145,33 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                time,
+            } => all::handle(year, release, time),
+            AppArguments::Download { days, force } => download::handle(days, force),
+            AppArguments::Encrypt => encrypt::handle(),
+            AppArguments::Migrate { dry_run } => migrate::handle(dry_run),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Scale { day, year, params } => scale::handle(day, year, &params),
+            AppArguments::Session {
+                token,
+                status,
+                force,
+            } => session::handle(token, status, force),
+            AppArguments::Today { watch } => today::handle(watch),
             AppArguments::Solve {
                 day,
+                year,
                 release,
                 time,
+                explain,
+                viz,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                params,
+            } => solve::handle(day, year, release, time, explain, viz, submit, &params),
         },
     };
 }
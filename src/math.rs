@@ -0,0 +1,421 @@
+/// Number theory helpers for modular puzzles.
+/// Everything is generic over the primitive integers (e.g. `u64`, `i64`, `i128`) and reports overflow
+/// as `None` instead of wrapping or panicking.
+use num::{
+    traits::{CheckedNeg, CheckedRem},
+    Integer, PrimInt, Signed,
+};
+
+/// Primitive integer types supported by this module.
+pub trait Int: PrimInt + Integer + CheckedRem + CheckedNeg {}
+
+impl<T: PrimInt + Integer + CheckedRem + CheckedNeg> Int for T {}
+
+fn checked_abs<T: Int>(value: T) -> Option<T> {
+    if value < T::zero() {
+        value.checked_neg()
+    } else {
+        Some(value)
+    }
+}
+
+/// Greatest common divisor, always non-negative.
+/// Returns `None` if the result is not representable, e.g. `gcd(i64::MIN, 0)`.
+pub fn gcd<T: Int>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+
+    while b != T::zero() {
+        // `checked_rem` only fails for `MIN % -1`, which is zero.
+        let r = a.checked_rem(&b).unwrap_or_else(T::zero);
+        a = b;
+        b = r;
+    }
+
+    checked_abs(a)
+}
+
+/// Least common multiple, always non-negative. Returns `None` on overflow.
+pub fn lcm<T: Int>(a: T, b: T) -> Option<T> {
+    if a == T::zero() || b == T::zero() {
+        return Some(T::zero());
+    }
+
+    let g = gcd(a, b)?;
+    checked_abs((a / g).checked_mul(&b)?)
+}
+
+/// Greatest common divisor of all `values`, `0` if there are none.
+pub fn gcd_all<T: Int>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::zero(), |acc, value| gcd(acc, value))
+}
+
+/// Least common multiple of all `values`, `1` if there are none. Returns `None` on overflow.
+pub fn lcm_all<T: Int>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |acc, value| lcm(acc, value))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the gcd of `a` and `b`.
+/// Returns `None` on overflow, e.g. for `T::min_value()`.
+pub fn extended_gcd<T: Int + Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    // `old - q * new`, the step shared by the remainders and both coefficients.
+    let step = |old: T, q: T, new: T| old.checked_sub(&q.checked_mul(&new)?);
+
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while r != T::zero() {
+        let q = old_r.checked_div(&r)?;
+        (old_r, r) = (r, step(old_r, q, r)?);
+        (old_x, x) = (x, step(old_x, q, x)?);
+        (old_y, y) = (y, step(old_y, q, y)?);
+    }
+
+    if old_r < T::zero() {
+        Some((
+            old_r.checked_neg()?,
+            old_x.checked_neg()?,
+            old_y.checked_neg()?,
+        ))
+    } else {
+        Some((old_r, old_x, old_y))
+    }
+}
+
+/// `(a + b) % m` for `a, b` in `0..m`, without overflowing.
+fn add_mod<T: Int>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a - b) % m` for `a, b` in `0..m`, without overflowing.
+fn sub_mod<T: Int>(a: T, b: T, m: T) -> T {
+    if a >= b {
+        a - b
+    } else {
+        m - (b - a)
+    }
+}
+
+/// `a * b` modulo `m`, in the range `0..m`. Falls back to double-and-add if the product would overflow.
+///
+/// # Panics
+/// Panics if `m` is not positive.
+pub fn mod_mul<T: Int>(a: T, b: T, m: T) -> T {
+    assert!(m > T::zero(), "modulus must be positive");
+
+    let (mut a, mut b) = (a.mod_floor(&m), b.mod_floor(&m));
+
+    if let Some(product) = a.checked_mul(&b) {
+        return product % m;
+    }
+
+    let mut result = T::zero();
+
+    while b > T::zero() {
+        if b.is_odd() {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b >> 1;
+    }
+
+    result
+}
+
+/// `base ^ exp` modulo `m`, in the range `0..m`.
+///
+/// # Panics
+/// Panics if `m` is not positive or `exp` is negative.
+pub fn mod_pow<T: Int>(base: T, exp: T, m: T) -> T {
+    assert!(exp >= T::zero(), "exponent must not be negative");
+
+    let (mut base, mut exp) = (base.mod_floor(&m), exp);
+    let mut result = T::one() % m;
+
+    while exp > T::zero() {
+        if exp.is_odd() {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp = exp >> 1;
+    }
+
+    result
+}
+
+/// The `x` in `0..m` with `a * x = 1 (mod m)`, if `a` and `m` are coprime.
+///
+/// # Panics
+/// Panics if `m` is not positive.
+pub fn mod_inverse<T: Int>(a: T, m: T) -> Option<T> {
+    assert!(m > T::zero(), "modulus must be positive");
+
+    // Extended Euclid with the coefficients kept in `0..m`, so this also works for unsigned types.
+    let (mut old_r, mut r) = (m, a.mod_floor(&m));
+    let (mut old_t, mut t) = (T::zero(), T::one() % m);
+
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_t, t) = (t, sub_mod(old_t, mod_mul(q, t, m), m));
+    }
+
+    (old_r == T::one()).then_some(old_t)
+}
+
+/// Merges `x = a1 (mod m1)` and `x = a2 (mod m2)` into `x = a (mod lcm(m1, m2))`.
+/// The moduli do not need to be coprime. Returns `None` if the congruences contradict each other
+/// or the combined modulus overflows.
+///
+/// # Panics
+/// Panics if a modulus is not positive.
+pub fn crt_pair<T: Int>((a1, m1): (T, T), (a2, m2): (T, T)) -> Option<(T, T)> {
+    assert!(m1 > T::zero() && m2 > T::zero(), "moduli must be positive");
+
+    let (a1, a2) = (a1.mod_floor(&m1), a2.mod_floor(&m2));
+    let g = gcd(m1, m2)?;
+    let diff = sub_mod(a2, a1 % m2, m2);
+
+    if diff % g != T::zero() {
+        return None;
+    }
+
+    // Solve `m1 * k = diff (mod m2)` for k, then x = a1 + m1 * k.
+    let reduced = m2 / g;
+    let k = mod_mul(diff / g, mod_inverse(m1 / g, reduced)?, reduced);
+    let modulus = m1.checked_mul(&reduced)?;
+    let a = a1.checked_add(&m1.checked_mul(&k)?)?;
+
+    Some((a, modulus))
+}
+
+/// Solves a system of congruences `x = a (mod m)`, see [`crt_pair`].
+/// Returns the smallest non-negative solution together with the combined modulus.
+pub fn crt<T: Int>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::zero(), T::one()), crt_pair)
+}
+
+/// Integer square root, i.e. the largest `x` with `x * x <= n`.
+///
+/// # Panics
+/// Panics if `n` is negative.
+pub fn isqrt<T: Int>(n: T) -> T {
+    assert!(n >= T::zero(), "square root of negative number");
+
+    if n < T::one() + T::one() {
+        return n;
+    }
+
+    // Start above the root and descend with Newton's method; this never overflows.
+    let bits = T::zero().count_zeros() - n.leading_zeros();
+    let mut x = T::one() << (bits / 2 + 1) as usize;
+
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Number of integers `h` in `0..=t` with `h * (t - h) > d`, e.g. the hold times that win a boat race
+/// of length `t` against a record distance `d`.
+/// The bounds come from the roots `(t ± sqrt(t² - 4d)) / 2`, computed with an exact integer square root.
+#[must_use]
+pub fn count_products_above(t: u64, d: u64) -> u64 {
+    let (t, d) = (u128::from(t), u128::from(d));
+    let beats = |h: u128| h * (t - h) > d;
+
+    let Some(discriminant) = (t * t).checked_sub(4 * d) else {
+        return 0;
+    };
+
+    // Start next to the smaller root, then step onto the first winning value. When the root is an
+    // integer, it only ties the record, so it must not be counted.
+    let mut low = (t - isqrt(discriminant)) / 2;
+
+    while low > 0 && beats(low - 1) {
+        low -= 1;
+    }
+
+    while low <= t / 2 && !beats(low) {
+        low += 1;
+    }
+
+    if low > t / 2 {
+        return 0;
+    }
+
+    // The winning values are symmetric around t / 2.
+    (t - 2 * low + 1) as u64
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::differential::Rng;
+
+    const CASES: usize = 2_000;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12_u64, 18), Some(6));
+        assert_eq!(gcd(-12_i64, 18), Some(6));
+        assert_eq!(gcd(0_i64, 0), Some(0));
+        assert_eq!(gcd(i64::MIN, 0), None);
+        assert_eq!(gcd(i64::MIN, -1), Some(1));
+        assert_eq!(lcm(4_u64, 6), Some(12));
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(lcm_all([2_u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+        assert_eq!(gcd_all([12_i128, -18, 30]), Some(6));
+    }
+
+    #[test]
+    fn prop_gcd_divides_and_lcm_is_multiple() {
+        let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..CASES {
+            let a = rng.below(1_000_000) + 1;
+            let b = rng.below(1_000_000) + 1;
+            let g = gcd(a, b).unwrap();
+            let l = lcm(a, b).unwrap();
+
+            assert_eq!(a % g, 0);
+            assert_eq!(b % g, 0);
+            assert_eq!(gcd(a / g, b / g), Some(1));
+            assert_eq!(u128::from(g) * u128::from(l), u128::from(a) * u128::from(b));
+        }
+    }
+
+    #[test]
+    fn prop_extended_gcd_is_bezout() {
+        let mut rng = Rng::new(42);
+
+        for _ in 0..CASES {
+            let a = rng.below(2_000_000) as i64 - 1_000_000;
+            let b = rng.below(2_000_000) as i64 - 1_000_000;
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+
+            assert_eq!(Some(g), gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+        assert_eq!(extended_gcd(i64::MAX, 1), Some((1, 0, 1)));
+    }
+
+    #[test]
+    fn prop_mod_mul_and_pow_match_wide_arithmetic() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..CASES {
+            let (a, b, e) = (rng.next_u64(), rng.next_u64(), rng.below(1_000));
+            let m = rng.next_u64() | 1;
+
+            let expected = (u128::from(a) * u128::from(b) % u128::from(m)) as u64;
+            assert_eq!(mod_mul(a, b, m), expected);
+
+            let expected = (0..e).fold(1 % u128::from(m), |acc, _| {
+                acc * u128::from(a % m) % u128::from(m)
+            });
+            assert_eq!(u128::from(mod_pow(a, e, m)), expected);
+        }
+
+        assert_eq!(mod_mul(-3_i64, 4, 5), 3);
+        assert_eq!(mod_pow(2_i128, 10, 1_000), 24);
+    }
+
+    #[test]
+    fn prop_mod_inverse() {
+        let mut rng = Rng::new(1234);
+
+        for _ in 0..CASES {
+            let m = rng.below(1_000_000) + 2;
+            let a = rng.next_u64();
+
+            match mod_inverse(a, m) {
+                Some(inverse) => assert_eq!(mod_mul(a, inverse, m), 1),
+                None => assert_ne!(gcd(a % m, m), Some(1)),
+            }
+        }
+
+        assert_eq!(mod_inverse(-3_i64, 7), Some(2));
+    }
+
+    #[test]
+    fn crt_examples() {
+        assert_eq!(crt([(2_u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1_i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1_i64, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1_i128, 10)]), Some((9, 10)));
+        assert_eq!(crt(Vec::<(u64, u64)>::new()), Some((0, 1)));
+        assert_eq!(crt([(0_u64, u64::MAX), (1, u64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn prop_crt_matches_brute_force() {
+        let mut rng = Rng::new(99);
+
+        for _ in 0..CASES {
+            let congruences = (0..3)
+                .map(|_| {
+                    let m = rng.below(30) + 1;
+                    (rng.below(100), m)
+                })
+                .collect::<Vec<(u64, u64)>>();
+
+            let period = lcm_all(congruences.iter().map(|c| c.1)).unwrap();
+            let brute_force =
+                (0..period).find(|x| congruences.iter().all(|&(a, m)| x % m == a % m));
+
+            assert_eq!(crt(congruences.clone()), brute_force.map(|x| (x, period)));
+        }
+    }
+
+    #[test]
+    fn prop_isqrt() {
+        let mut rng = Rng::new(5);
+
+        for n in (0..1_000_u64).chain((0..CASES).map(|_| rng.next_u64())) {
+            let root = u128::from(isqrt(n));
+            assert!(root * root <= u128::from(n));
+            assert!((root + 1) * (root + 1) > u128::from(n));
+        }
+
+        assert_eq!(isqrt(u64::MAX), u64::from(u32::MAX));
+        assert_eq!(isqrt(i128::MAX), 13_043_817_825_332_782_212);
+        assert_eq!(isqrt(255_u8), 15);
+    }
+
+    #[test]
+    fn count_products_above_matches_brute_force() {
+        let brute_force = |t: u64, d: u64| (0..=t).filter(|h| h * (t - h) > d).count() as u64;
+
+        for t in 0..60 {
+            for d in 0..=(t * t / 4 + 1) {
+                assert_eq!(
+                    count_products_above(t, d),
+                    brute_force(t, d),
+                    "t = {t}, d = {d}"
+                );
+            }
+        }
+
+        // The roots of 30 and 200 are exactly 10 and 20, which only tie the record.
+        assert_eq!(count_products_above(30, 200), 9);
+        assert_eq!(count_products_above(71_530, 940_200), 71_503);
+        assert_eq!(count_products_above(u64::MAX, 0), u64::MAX - 1);
+    }
+}
@@ -0,0 +1,129 @@
+/// Memoization for recursive functions, so dynamic programming solutions don't need hand-rolled caches.
+///
+/// The wrapped function receives a handle to call itself through the cache, plus its argument.
+/// Several arguments are passed as a tuple:
+///
+/// ```ignore
+/// let mut fib = memoize(|fib: &mut dyn FnMut(u64) -> u64, n: u64| {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// });
+/// assert_eq!(fib.call(90), 2_880_067_194_370_816_120);
+/// ```
+use rustc_hash::FxHasher;
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, BuildHasherDefault, Hash},
+};
+
+/// Hasher for [`memoize_fast`]. Much quicker than the default hasher for small keys such as integers,
+/// but not resistant to collisions crafted on purpose, which does not matter for puzzle inputs.
+pub type FastHasher = BuildHasherDefault<FxHasher>;
+
+/// A function wrapped with a cache, see [`memoize`].
+pub struct Memoized<K, V, F, S = RandomState> {
+    cache: HashMap<K, V, S>,
+    f: F,
+}
+
+impl<K, V, F, S> Memoized<K, V, F, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    S: BuildHasher,
+{
+    /// Returns the cached result for `key`, computing it first if needed.
+    pub fn call(&mut self, key: K) -> V {
+        compute(&self.f, &mut self.cache, key)
+    }
+
+    /// Number of cached results.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+fn compute<K, V, F, S>(f: &F, cache: &mut HashMap<K, V, S>, key: K) -> V
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    S: BuildHasher,
+{
+    if let Some(value) = cache.get(&key) {
+        return value.clone();
+    }
+
+    let value = f(&mut |k| compute(f, cache, k), key.clone());
+    cache.insert(key, value.clone());
+    value
+}
+
+/// Wraps a recursive function with a cache keyed by its argument.
+pub fn memoize<K, V, F>(f: F) -> Memoized<K, V, F>
+where
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    Memoized {
+        cache: HashMap::new(),
+        f,
+    }
+}
+
+/// Like [`memoize`], but hashes keys with the faster [`FastHasher`].
+pub fn memoize_fast<K, V, F>(f: F) -> Memoized<K, V, F, FastHasher>
+where
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    Memoized {
+        cache: HashMap::default(),
+        f,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{memoize, memoize_fast};
+    use std::cell::Cell;
+
+    #[test]
+    fn caches_recursive_calls() {
+        let calls = Cell::new(0);
+        let mut fib = memoize(|fib: &mut dyn FnMut(u64) -> u64, n: u64| {
+            calls.set(calls.get() + 1);
+            if n < 2 {
+                n
+            } else {
+                fib(n - 1) + fib(n - 2)
+            }
+        });
+
+        assert_eq!(fib.call(90), 2_880_067_194_370_816_120);
+        assert_eq!(calls.get(), 91);
+        assert_eq!(fib.len(), 91);
+
+        assert_eq!(fib.call(50), 12_586_269_025);
+        assert_eq!(calls.get(), 91);
+    }
+
+    #[test]
+    fn supports_tuple_keys() {
+        // Number of lattice paths through a grid.
+        let mut paths = memoize_fast(|paths: &mut dyn FnMut((u32, u32)) -> u64, (x, y)| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                paths((x - 1, y)) + paths((x, y - 1))
+            }
+        });
+
+        assert_eq!(paths.call((2, 2)), 6);
+        assert_eq!(paths.call((16, 16)), 601_080_390);
+    }
+}
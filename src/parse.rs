@@ -0,0 +1,161 @@
+/// Fast, allocation-free helpers for pulling integers out of puzzle input.
+/// Most inputs only need "all the numbers on this line", so these scan the raw bytes
+/// instead of splitting on whitespace and parsing every token.
+use std::marker::PhantomData;
+
+/// An integer type that can be assembled digit by digit.
+pub trait ParseInt: Copy {
+    /// Whether a `-` directly in front of a number makes it negative.
+    const SIGNED: bool;
+    const ZERO: Self;
+
+    #[must_use]
+    fn push_digit(self, digit: u8) -> Self;
+
+    #[must_use]
+    fn negate(self) -> Self;
+}
+
+macro_rules! impl_parse_int {
+    ($signed:expr; $($t:ty),*) => {
+        $(
+            impl ParseInt for $t {
+                const SIGNED: bool = $signed;
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn push_digit(self, digit: u8) -> Self {
+                    self.wrapping_mul(10).wrapping_add(<$t>::from(digit))
+                }
+
+                #[inline]
+                fn negate(self) -> Self {
+                    self.wrapping_neg()
+                }
+            }
+        )*
+    };
+}
+
+impl_parse_int!(false; u8, u16, u32, u64, u128, usize);
+impl_parse_int!(true; i16, i32, i64, i128, isize);
+
+impl ParseInt for i8 {
+    const SIGNED: bool = true;
+    const ZERO: Self = 0;
+
+    #[inline]
+    fn push_digit(self, digit: u8) -> Self {
+        #[allow(clippy::cast_possible_wrap)]
+        self.wrapping_mul(10).wrapping_add(digit as i8)
+    }
+
+    #[inline]
+    fn negate(self) -> Self {
+        self.wrapping_neg()
+    }
+}
+
+/// Iterator over the integers contained in a string, see [`ints`].
+#[derive(Clone)]
+pub struct Ints<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: ParseInt> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let bytes = self.bytes;
+        let mut pos = self.pos;
+
+        while pos < bytes.len() && !bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+
+        if pos == bytes.len() {
+            self.pos = pos;
+            return None;
+        }
+
+        let is_negative = T::SIGNED && pos > 0 && bytes[pos - 1] == b'-';
+        let mut value = T::ZERO;
+
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            value = value.push_digit(bytes[pos] - b'0');
+            pos += 1;
+        }
+
+        self.pos = pos;
+        Some(if is_negative { value.negate() } else { value })
+    }
+}
+
+/// Returns an iterator over every integer in `s`, in order of appearance.
+/// Anything that is not a digit acts as a separator. For signed types, a `-` directly
+/// in front of a number negates it; unsigned types ignore it.
+/// Values that do not fit into `T` wrap around silently.
+#[must_use]
+pub fn ints<T: ParseInt>(s: &str) -> Ints<'_, T> {
+    Ints {
+        bytes: s.as_bytes(),
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
+
+/// Like [`ints`], but yields one iterator per line of `s`.
+pub fn ints_per_line<T: ParseInt>(s: &str) -> impl Iterator<Item = Ints<'_, T>> {
+    s.lines().map(ints)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ints, ints_per_line};
+
+    #[test]
+    fn parses_unsigned() {
+        let result: Vec<u32> = ints("Card   1: 41 48 83 86 17 | 83 86  6 31").collect();
+        assert_eq!(result, vec![1, 41, 48, 83, 86, 17, 83, 86, 6, 31]);
+    }
+
+    #[test]
+    fn parses_signed() {
+        let result: Vec<i64> = ints("10 -3 0 -0 4-5").collect();
+        assert_eq!(result, vec![10, -3, 0, 0, 4, -5]);
+    }
+
+    #[test]
+    fn unsigned_ignores_minus() {
+        let result: Vec<u64> = ints("-1 -22").collect();
+        assert_eq!(result, vec![1, 22]);
+    }
+
+    #[test]
+    fn handles_empty_and_trailing_input() {
+        assert_eq!(ints::<u8>("").count(), 0);
+        assert_eq!(ints::<u8>("no numbers -").count(), 0);
+        assert_eq!(ints::<u8>("7").collect::<Vec<_>>(), vec![7]);
+    }
+
+    #[test]
+    fn groups_by_line() {
+        let result: Vec<Vec<i32>> = ints_per_line("0 3 6\n\n-1 -2\n")
+            .map(Iterator::collect)
+            .collect();
+        assert_eq!(result, vec![vec![0, 3, 6], vec![], vec![-1, -2]]);
+    }
+
+    #[test]
+    fn matches_split_whitespace() {
+        let input = "1 3 6 10 15 21\n10 13 16 21 30 45\n-4 -7 2 123456789";
+        let expected: Vec<i64> = input
+            .split_whitespace()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert_eq!(ints::<i64>(input).collect::<Vec<_>>(), expected);
+    }
+}
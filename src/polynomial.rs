@@ -0,0 +1,225 @@
+/// Exact extrapolation of sequences that follow a polynomial, via finite differences.
+/// All arithmetic is checked `i128`, so every method returns `None` instead of overflowing.
+use crate::math::{gcd, gcd_all};
+
+/// Finite-difference table of a sequence `f(0), f(1), ..., f(n - 1)`.
+/// Only the leading diagonal `Δ^j f(0)` is kept, which is all Newton's forward formula needs:
+/// `f(x) = Σ binom(x, j) * Δ^j f(0)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifferenceTable {
+    coefficients: Vec<i128>,
+    len: usize,
+}
+
+impl DifferenceTable {
+    /// Builds the table. Differencing stops at the first row that is all zeroes.
+    /// Returns `None` if `values` is empty or a difference overflows.
+    pub fn new<T: Copy + Into<i128>>(values: &[T]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut row = values.iter().map(|&v| v.into()).collect::<Vec<i128>>();
+        let mut coefficients = vec![];
+
+        while !row.iter().all(|&v| v == 0) {
+            coefficients.push(row[0]);
+            row = row
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]))
+                .collect::<Option<Vec<i128>>>()?;
+        }
+
+        Some(Self {
+            coefficients,
+            len: values.len(),
+        })
+    }
+
+    /// Newton forward coefficients `Δ^j f(0)`, for `j` up to the degree.
+    #[must_use]
+    pub fn newton_coefficients(&self) -> &[i128] {
+        &self.coefficients
+    }
+
+    /// Degree of the polynomial through the values. The all-zero sequence has degree 0.
+    #[must_use]
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    /// Value of the polynomial at index `x`, which may lie outside of `0..n` or be negative.
+    #[must_use]
+    pub fn value_at(&self, x: i128) -> Option<i128> {
+        let mut binomial: i128 = 1;
+        let mut sum: i128 = 0;
+
+        for (j, &coefficient) in (0_i128..).zip(&self.coefficients) {
+            if j > 0 {
+                // binom(x, j) = binom(x, j - 1) * (x - j + 1) / j, the division is exact.
+                binomial = binomial.checked_mul(x.checked_sub(j - 1)?)? / j;
+            }
+            sum = sum.checked_add(binomial.checked_mul(coefficient)?)?;
+        }
+
+        Some(sum)
+    }
+
+    /// Value `steps` positions after the last known value.
+    #[must_use]
+    pub fn forward(&self, steps: usize) -> Option<i128> {
+        let x = i128::try_from(self.len - 1 + steps).ok()?;
+        self.value_at(x)
+    }
+
+    /// Value `steps` positions before the first known value.
+    #[must_use]
+    pub fn backward(&self, steps: usize) -> Option<i128> {
+        let x = i128::try_from(steps).ok()?;
+        self.value_at(-x)
+    }
+
+    /// The polynomial through the values in the power basis.
+    #[must_use]
+    pub fn polynomial(&self) -> Option<Polynomial> {
+        let degree = self.degree();
+        let denominator = (1..=degree as i128).try_fold(1_i128, i128::checked_mul)?;
+
+        let mut numerators = vec![0_i128; degree + 1];
+        // Falling factorial x (x - 1) ... (x - j + 1), in the power basis.
+        let mut falling = vec![1_i128];
+        // denominator / j!
+        let mut scale = denominator;
+
+        for (j, &coefficient) in self.coefficients.iter().enumerate() {
+            if j > 0 {
+                scale /= j as i128;
+                falling = multiply_by_linear(&falling, -(j as i128 - 1))?;
+            }
+
+            let factor = coefficient.checked_mul(scale)?;
+            for (numerator, &c) in numerators.iter_mut().zip(&falling) {
+                *numerator = numerator.checked_add(c.checked_mul(factor)?)?;
+            }
+        }
+
+        Some(Polynomial::new(numerators, denominator))
+    }
+}
+
+/// Multiplies a power-basis polynomial by `(x + c)`.
+fn multiply_by_linear(coefficients: &[i128], c: i128) -> Option<Vec<i128>> {
+    let mut result = vec![0_i128; coefficients.len() + 1];
+
+    for (i, &a) in coefficients.iter().enumerate() {
+        result[i] = result[i].checked_add(a.checked_mul(c)?)?;
+        result[i + 1] = result[i + 1].checked_add(a)?;
+    }
+
+    Some(result)
+}
+
+/// Polynomial with rational coefficients `numerators[i] / denominator` for `x^i`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    pub numerators: Vec<i128>,
+    pub denominator: i128,
+}
+
+impl Polynomial {
+    /// Creates a polynomial, reducing the fraction and trimming leading zero coefficients.
+    #[must_use]
+    pub fn new(mut numerators: Vec<i128>, denominator: i128) -> Self {
+        while numerators.len() > 1 && numerators.last() == Some(&0) {
+            numerators.pop();
+        }
+
+        let common = gcd_all(numerators.iter().copied())
+            .and_then(|g| gcd(g, denominator))
+            .filter(|&g| g > 1)
+            .unwrap_or(1);
+
+        let sign = if denominator < 0 { -1 } else { 1 };
+
+        Self {
+            numerators: numerators.iter().map(|n| sign * n / common).collect(),
+            denominator: sign * denominator / common,
+        }
+    }
+
+    /// Evaluates the polynomial at `x`. Returns `None` on overflow or if the result is not an integer.
+    #[must_use]
+    pub fn eval(&self, x: i128) -> Option<i128> {
+        let sum = self
+            .numerators
+            .iter()
+            .rev()
+            .try_fold(0_i128, |acc, &c| acc.checked_mul(x)?.checked_add(c))?;
+
+        (sum % self.denominator == 0).then(|| sum / self.denominator)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DifferenceTable, Polynomial};
+
+    #[test]
+    fn finds_degree() {
+        assert_eq!(
+            DifferenceTable::new(&[0, 3, 6, 9, 12, 15])
+                .unwrap()
+                .degree(),
+            1
+        );
+        assert_eq!(
+            DifferenceTable::new(&[1, 3, 6, 10, 15, 21])
+                .unwrap()
+                .degree(),
+            2
+        );
+        assert_eq!(DifferenceTable::new(&[0, 0, 0]).unwrap().degree(), 0);
+        assert_eq!(DifferenceTable::new::<i64>(&[]), None);
+    }
+
+    #[test]
+    fn extrapolates_in_both_directions() {
+        let table = DifferenceTable::new(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(table.forward(1), Some(68));
+        assert_eq!(table.backward(1), Some(5));
+        assert_eq!(table.forward(0), Some(45));
+        assert_eq!(table.backward(0), Some(10));
+
+        let squares = DifferenceTable::new(&[0, 1, 4, 9]).unwrap();
+        assert_eq!(squares.forward(997), Some(1_000_000));
+        assert_eq!(squares.backward(1_000), Some(1_000_000));
+    }
+
+    #[test]
+    fn matches_iterated_differences() {
+        let values = [3_i64, -7, 12, 40, -2, 9, 100];
+        let table = DifferenceTable::new(&values).unwrap();
+
+        for (x, &v) in values.iter().enumerate() {
+            assert_eq!(table.value_at(x as i128), Some(i128::from(v)));
+        }
+    }
+
+    #[test]
+    fn builds_power_basis() {
+        // (x + 1) (x + 2) / 2
+        let table = DifferenceTable::new(&[1, 3, 6, 10, 15, 21]).unwrap();
+        let polynomial = table.polynomial().unwrap();
+        assert_eq!(polynomial, Polynomial::new(vec![2, 3, 1], 2));
+
+        for x in -20..20 {
+            assert_eq!(polynomial.eval(x), table.value_at(x));
+        }
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let table = DifferenceTable::new(&[i64::MIN, i64::MAX, i64::MIN]).unwrap();
+        assert_eq!(table.forward(usize::MAX / 2), None);
+    }
+}
@@ -1,36 +1,139 @@
 /// Wrapper module around the "aoc-cli" command-line.
+/// The year, session file and file locations come from the project config.
 use std::{
     fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
     process::{Command, Output, Stdio},
 };
 
+use crate::template::{config::Config, encryption, submission::Verdict};
+
+/// The arguments the template passes need at least this version.
+const MIN_VERSION: (u32, u32) = (0, 12);
+const INSTALL: &str = "cargo install aoc-cli --version 0.12.0";
+
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
-    IoError,
+    UnsupportedVersion(String),
+    CommandNotCallable {
+        invocation: String,
+        source: io::Error,
+    },
+    BadExitStatus {
+        invocation: String,
+        code: Option<i32>,
+        stderr: String,
+    },
+    IoError {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// aoc-cli exited successfully, but a file it should have written is missing or empty.
+    MissingOutput(PathBuf),
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::CommandNotFound => write!(
+                f,
+                "aoc-cli is not present in environment. Install it with `{INSTALL}`."
+            ),
+            AocCommandError::UnsupportedVersion(version) => write!(
+                f,
+                "aoc-cli {version} is not supported. Install a supported version with `{INSTALL}`."
+            ),
+            AocCommandError::CommandNotCallable { invocation, source } => {
+                write!(f, "aoc-cli could not be called as `{invocation}`: {source}")
+            }
+            AocCommandError::BadExitStatus {
+                invocation,
+                code,
+                stderr,
+            } => {
+                match code {
+                    Some(code) => write!(f, "`{invocation}` exited with status {code}.")?,
+                    None => write!(f, "`{invocation}` was terminated by a signal.")?,
+                }
+                if !stderr.trim().is_empty() {
+                    write!(f, "\n{}", stderr.trim_end())?;
+                }
+                if let Some(hint) = hint(stderr) {
+                    write!(f, "\nHint: {hint}")?;
+                }
+                Ok(())
             }
-            AocCommandError::IoError => write!(f, "could not write output files to file system."),
+            AocCommandError::IoError { path, source } => {
+                write!(f, "could not write to \"{}\": {source}", path.display())
+            }
+            AocCommandError::MissingOutput(path) => write!(
+                f,
+                "aoc-cli reported success, but \"{}\" is missing or empty.",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AocCommandError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocCommandError::CommandNotCallable { source, .. }
+            | AocCommandError::IoError { source, .. } => Some(source),
+            _ => None,
         }
     }
 }
 
+/// What to do about the common failures, recognized by aoc-cli's error messages.
+fn hint(stderr: &str) -> Option<String> {
+    let stderr = stderr.to_lowercase();
+
+    if stderr.contains("session cookie") || stderr.contains("session file") {
+        Some("store your session cookie with `cargo session`.".into())
+    } else if stderr.contains("still locked") || stderr.contains("not unlocked") {
+        Some("the puzzle is not unlocked yet, `cargo today` counts down to the next one.".into())
+    } else if stderr.contains("unexpected argument")
+        || stderr.contains("wasn't expected")
+        || stderr.contains("unrecognized")
+    {
+        Some(format!(
+            "aoc-cli does not support the arguments of this template, install it with `{INSTALL}`."
+        ))
+    } else if ["http request", "dns", "connect", "timed out", "network"]
+        .iter()
+        .any(|error| stderr.contains(error))
+    {
+        Some("the site could not be reached, check your network connection.".into())
+    } else {
+        None
+    }
+}
+
+/// Checks that aoc-cli is installed in a supported version.
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    let output = Command::new("aoc")
         .arg("-V")
         .output()
         .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+
+    let version = String::from_utf8_lossy(&output.stdout);
+
+    match parse_version(&version) {
+        Some(found) if found < MIN_VERSION => Err(AocCommandError::UnsupportedVersion(
+            version.trim().trim_start_matches("aoc-cli ").into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The major and minor version in the output of `aoc -V`, e.g. "aoc-cli 0.12.0".
+fn parse_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.split_whitespace().last()?;
+    let mut parts = version.split('.').map(str::parse::<u32>);
+    Some((parts.next()?.ok()?, parts.next()?.ok()?))
 }
 
 pub fn read(day: u8) -> Result<Output, AocCommandError> {
@@ -46,13 +149,17 @@ pub fn read(day: u8) -> Result<Output, AocCommandError> {
         day,
     );
 
-    call_aoc_cli(&args)
+    call_aoc_cli(&args, Stdio::inherit())
 }
 
 pub fn download(day: u8) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
+    for path in [&input_path, &puzzle_path] {
+        create_parent_dir(path)?;
+    }
+
     let args = build_args(
         "download",
         &[
@@ -65,63 +172,215 @@ pub fn download(day: u8) -> Result<Output, AocCommandError> {
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
+    let output = call_aoc_cli(&args, Stdio::inherit())?;
+    verify_output(&input_path)?;
+    verify_output(&puzzle_path)?;
+    encryption::store_download(Path::new(&input_path), "inputs");
+    encryption::store_download(Path::new(&puzzle_path), "puzzles");
+
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
     Ok(output)
 }
 
-pub fn submit(day: u8, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Downloads only the description of `day`, e.g. when the input was fetched separately.
+pub fn download_puzzle(day: u8) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(day);
+
+    create_parent_dir(&puzzle_path)?;
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            puzzle_path.to_string(),
+        ],
+        day,
+    );
+
+    let output = call_aoc_cli(&args, Stdio::inherit())?;
+    verify_output(&puzzle_path)?;
+    encryption::store_download(Path::new(&puzzle_path), "puzzles");
+    Ok(output)
+}
+
+/// Submits `result` and classifies the response of the site.
+pub fn submit(day: u8, part: u8, result: &str) -> Result<Verdict, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: u8) -> String {
-    let day_padded = format!("{day:02}");
-    format!("data/inputs/{day_padded}.txt")
+    let output = call_aoc_cli(&args, Stdio::piped())?;
+
+    // the description contains part 2 once part 1 is solved.
+    let part_two_unlocked = encryption::read_to_string(Path::new(&get_puzzle_path(day)))
+        .is_ok_and(|description| description.contains("--- Part Two ---"));
+
+    Ok(Verdict::parse(
+        &String::from_utf8_lossy(&output.stdout),
+        part,
+        part_two_unlocked,
+    ))
 }
 
-fn get_puzzle_path(day: u8) -> String {
+#[must_use]
+pub fn get_input_path(day: u8) -> String {
     let day_padded = format!("{day:02}");
-    format!("data/puzzles/{day_padded}.md")
+    let dir = Config::get().data_dir("inputs");
+    format!("{}/{day_padded}.txt", dir.display())
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+#[must_use]
+pub fn get_puzzle_path(day: u8) -> String {
+    let day_padded = format!("{day:02}");
+    let dir = Config::get().data_dir("puzzles");
+    format!("{}/{day_padded}.md", dir.display())
 }
 
 fn build_args(command: &str, args: &[String], day: u8) -> Vec<String> {
+    let config = Config::get();
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = config.year {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
 
+    if let Some(session_path) = config.session_path() {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_path.to_string_lossy().into());
+    }
+
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
+fn create_parent_dir(path: &str) -> Result<(), AocCommandError> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir).map_err(|source| AocCommandError::IoError {
+            path: dir.into(),
+            source,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// aoc-cli exits successfully in some cases where it did not write a file.
+fn verify_output(path: &str) -> Result<(), AocCommandError> {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.len() > 0 => Ok(()),
+        _ => Err(AocCommandError::MissingOutput(path.into())),
+    }
+}
+
+/// The command-line of a call, to repeat it by hand.
+fn format_invocation(args: &[String]) -> String {
+    let args = args.iter().map(|arg| {
+        if arg.is_empty() || arg.contains(char::is_whitespace) {
+            format!("{arg:?}")
+        } else {
+            arg.clone()
+        }
+    });
+
+    std::iter::once("aoc".to_string())
+        .chain(args)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Calls aoc-cli with `stdout`. Its log on stderr is shown on success and kept in the error on
+/// failure.
+fn call_aoc_cli(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
+    let invocation = format_invocation(args);
+
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stdout(stdout)
+        .stderr(Stdio::piped())
         .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+        .map_err(|source| AocCommandError::CommandNotCallable {
+            invocation: invocation.clone(),
+            source,
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
     if output.status.success() {
+        eprint!("{stderr}");
         Ok(output)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(AocCommandError::BadExitStatus {
+            invocation,
+            code: output.status.code(),
+            stderr,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_invocation, hint, parse_version, AocCommandError, INSTALL};
+
+    #[test]
+    fn parses_version() {
+        assert_eq!(parse_version("aoc-cli 0.12.0\n"), Some((0, 12)));
+        assert_eq!(parse_version("aoc-cli 0.5.1"), Some((0, 5)));
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[test]
+    fn formats_invocation() {
+        let args = ["--session-file", "/my dir/session", "--day", "1", "read"].map(String::from);
+        assert_eq!(
+            format_invocation(&args),
+            "aoc --session-file \"/my dir/session\" --day 1 read"
+        );
+    }
+
+    #[test]
+    fn hints_at_common_failures() {
+        let cases = [
+            (
+                "[ERROR aoc] 🔔 Failed to read session cookie from '/x' file",
+                "cargo session",
+            ),
+            (
+                "[ERROR aoc] 🔔 Puzzle 25 of 2030 is still locked",
+                "cargo today",
+            ),
+            ("error: unexpected argument '--puzzle-only' found", INSTALL),
+            (
+                "[ERROR aoc] 🔔 HTTP request error: error sending request",
+                "network",
+            ),
+        ];
+
+        for (stderr, expected) in cases {
+            assert!(
+                hint(stderr).is_some_and(|hint| hint.contains(expected)),
+                "{stderr}"
+            );
+        }
+        assert_eq!(hint("something else"), None);
+    }
+
+    #[test]
+    fn describes_bad_exit_status() {
+        let error = AocCommandError::BadExitStatus {
+            invocation: "aoc --day 1 download".into(),
+            code: Some(2),
+            stderr: "[ERROR aoc] 🔔 Puzzle 1 of 2030 is still locked\n".into(),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "`aoc --day 1 download` exited with status 2.\n[ERROR aoc] 🔔 Puzzle 1 of 2030 is still locked\nHint: the puzzle is not unlocked yet, `cargo today` counts down to the next one."
+        );
     }
 }
@@ -0,0 +1,123 @@
+/// Checksums of downloaded inputs, to tell when an input was modified after its download.
+/// They are kept in a `.checksums` file next to the inputs, one `<file> <checksum>` line per input.
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const CHECKSUMS_FILE: &str = ".checksums";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Unchanged,
+    Modified,
+    /// The file was not downloaded by the template, or before checksums were recorded.
+    Unknown,
+}
+
+/// 64-bit FNV-1a, which is stable across Rust versions unlike the std hashers.
+#[must_use]
+pub fn checksum(contents: &[u8]) -> u64 {
+    contents.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn checksums_path(dir: &Path) -> PathBuf {
+    dir.join(CHECKSUMS_FILE)
+}
+
+fn parse(contents: &str) -> BTreeMap<String, u64> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (name, checksum) = line.split_once(' ')?;
+            let checksum = u64::from_str_radix(checksum.trim(), 16).ok()?;
+            Some((name.into(), checksum))
+        })
+        .collect()
+}
+
+fn serialize(checksums: &BTreeMap<String, u64>) -> String {
+    checksums
+        .iter()
+        .map(|(name, checksum)| format!("{name} {checksum:016x}\n"))
+        .collect()
+}
+
+/// Records the checksum of `file`, replacing the one recorded before.
+pub fn record(file: &Path) -> io::Result<()> {
+    let (dir, name) = split(file);
+    let mut checksums = parse(&fs::read_to_string(checksums_path(dir)).unwrap_or_default());
+    checksums.insert(name, checksum(&fs::read(file)?));
+    fs::write(checksums_path(dir), serialize(&checksums))
+}
+
+/// Compares `file` to its recorded checksum.
+pub fn verify(file: &Path) -> io::Result<Status> {
+    let (dir, name) = split(file);
+    let checksums = parse(&fs::read_to_string(checksums_path(dir)).unwrap_or_default());
+
+    let Some(expected) = checksums.get(&name) else {
+        return Ok(Status::Unknown);
+    };
+
+    Ok(if checksum(&fs::read(file)?) == *expected {
+        Status::Unchanged
+    } else {
+        Status::Modified
+    })
+}
+
+fn split(file: &Path) -> (&Path, String) {
+    let dir = file.parent().unwrap_or(Path::new("."));
+    let name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().into())
+        .unwrap_or_default();
+    (dir, name)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{checksum, parse, record, serialize, verify, Status};
+    use std::fs;
+
+    #[test]
+    fn computes_fnv1a() {
+        assert_eq!(checksum(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(checksum(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(checksum(b"1\n2\n"), checksum(b"1\n2"));
+    }
+
+    #[test]
+    fn roundtrips_checksums() {
+        let checksums = parse("01.txt 00000000000000ff\n02.txt af63dc4c8601ec8c\ninvalid\n");
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums["01.txt"], 255);
+        assert_eq!(
+            serialize(&checksums),
+            "01.txt 00000000000000ff\n02.txt af63dc4c8601ec8c\n"
+        );
+    }
+
+    #[test]
+    fn detects_modifications() {
+        let dir = std::env::temp_dir().join(format!("aoc-checksums-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("01.txt");
+
+        fs::write(&file, "1\n2\n").unwrap();
+        let unknown = verify(&file).unwrap();
+        record(&file).unwrap();
+        let unchanged = verify(&file).unwrap();
+        fs::write(&file, "1\n3\n").unwrap();
+        let modified = verify(&file).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(unknown, Status::Unknown);
+        assert_eq!(unchanged, Status::Unchanged);
+        assert_eq!(modified, Status::Modified);
+    }
+}
@@ -1,11 +1,47 @@
 use std::io;
 
 use crate::template::{
+    config::{Config, Layout},
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn handle(is_release: bool, is_timed: bool) {
+/// Runs all solutions of the selected year. In the multi-year layout, all years are run unless
+/// `year` is passed, and each year gets its own README table.
+pub fn handle(year: Option<u16>, is_release: bool, is_timed: bool) {
+    let config = Config::get();
+
+    let configs = match (config.layout, year) {
+        (Layout::MultiYear, None) => config
+            .solved_years()
+            .into_iter()
+            .map(|year| config.for_year(year))
+            .collect(),
+        _ => vec![config.clone()],
+    };
+
+    let mut total_millis = 0_f64;
+
+    for (i, config) in configs.iter().enumerate() {
+        if config.layout == Layout::MultiYear {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}=== {} ==={ANSI_RESET}\n", config.year.unwrap());
+        }
+
+        total_millis += run_year(config, is_release, is_timed);
+    }
+
+    if is_timed && configs.len() > 1 {
+        println!(
+            "\n{ANSI_BOLD}Total across years:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+        );
+    }
+}
+
+/// Runs the solutions of the year selected in `config` and returns their total time in ms.
+fn run_year(config: &Config, is_release: bool, is_timed: bool) -> f64 {
     let mut timings: Vec<Timings> = vec![];
 
     (1..=25).for_each(|day| {
@@ -16,23 +52,23 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(config, day, is_timed, is_release).unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let val = child_commands::parse_exec_time(&output, day.into());
             timings.push(val);
         }
     });
 
-    if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+    let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
+    if is_timed {
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+            match readme_benchmarks::update(config, timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -40,6 +76,8 @@ pub fn handle(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    total_millis
 }
 
 #[derive(Debug)]
@@ -55,16 +93,11 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: usize) -> String {
-    let day_padded = format!("{day:02}");
-    format!("./src/bin/{day_padded}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::Error;
+    use crate::template::config::Config;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -74,18 +107,19 @@ mod child_commands {
 
     /// Run the solution bin for a given day
     pub fn run_solution(
-        day: usize,
+        config: &Config,
+        day: u8,
         is_timed: bool,
         is_release: bool,
     ) -> Result<Vec<String>, Error> {
-        let day_padded = format!("{day:02}");
+        let bin_name = config.bin_name(day);
 
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&config.bin_path(day)).exists() {
             return Ok(vec![]);
         }
 
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
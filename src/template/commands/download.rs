@@ -1,14 +1,198 @@
-use crate::template::aoc_cli;
-use std::process;
+use crate::template::{
+    aoc_cli, checksums, commands::today, config::Config, encryption, http, session, ANSI_BOLD,
+    ANSI_RESET,
+};
+use std::{fs, path::Path, process, str::FromStr, thread, time::Duration};
 
-pub fn handle(day: u8) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Pause between days when downloading several, to go easy on the site.
+const THROTTLE: Duration = Duration::from_secs(3);
+
+/// The days to download: `5`, a range like `1-10`, or all unlocked days with `--all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Days {
+    One(u8),
+    Range(u8, u8),
+    All,
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| match day.trim().parse::<u8>() {
+            Ok(day @ 1..=25) => Ok(day),
+            _ => Err(format!(
+                "expected a day from 1 to 25 or a range like 1-10, found \"{s}\""
+            )),
+        };
+
+        match s.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse_day(first)?, parse_day(last)?);
+                if first > last {
+                    return Err(format!("the range {s} is empty"));
+                }
+                Ok(Days::Range(first, last))
+            }
+            None => Ok(Days::One(parse_day(s)?)),
+        }
+    }
+}
+
+/// Downloads the input and description of the selected days.
+/// Days with an input are skipped unless `force` is set. Several days are fetched one by one with
+/// a pause in between.
+pub fn handle(days: Days, force: bool) {
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
+    session::preflight();
+
+    match days {
+        Days::One(day) => download_one(day, force),
+        Days::Range(first, last) => download_many(first, Some(last), force),
+        Days::All => download_many(1, None, force),
+    }
+}
+
+fn download_one(day: u8, force: bool) {
+    if !force && is_skipped(day, Path::new(&aoc_cli::get_input_path(day))) {
+        println!("Pass --force to download it again.");
+        return;
+    }
+
     if let Err(e) = aoc_cli::download(day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    if let Err(e) = checksums::record(Path::new(&aoc_cli::get_input_path(day))) {
+        eprintln!("Warning: could not record the checksum of the input: {e}");
+    }
+}
+
+/// Downloads the days from `first` to `last`, or to the last day of the event without one.
+fn download_many(first: u8, last: Option<u8>, force: bool) {
+    let config = Config::get();
+    let year = config.year.unwrap_or_else(today::latest_event);
+    let (days, unlocked) = (config.days(year), today::unlocked_days(year));
+    let token = match session::load() {
+        Ok(session) => session.token,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    let (mut downloaded, mut skipped, mut failed) = (0, 0, vec![]);
+
+    for day in first..=last.unwrap_or(days) {
+        if day > days {
+            println!("The {year} event has {days} days, stopping.");
+            break;
+        }
+
+        if day > unlocked {
+            println!("Day {day} of {year} is not unlocked yet, stopping.");
+            break;
+        }
+
+        let input_path = aoc_cli::get_input_path(day);
+        let input_path = Path::new(&input_path);
+
+        if !force && is_skipped(day, input_path) {
+            skipped += 1;
+            continue;
+        }
+
+        if downloaded > 0 || !failed.is_empty() {
+            thread::sleep(THROTTLE);
+        }
+
+        println!("{ANSI_BOLD}Downloading day {day} of {year}{ANSI_RESET}");
+
+        match download_day(year, day, &token, input_path) {
+            Ok(()) => downloaded += 1,
+            Err(e) => {
+                eprintln!("Failed to download day {day}: {e}");
+                failed.push(day);
+            }
+        }
+    }
+
+    println!("---");
+    println!("🎄 Downloaded {downloaded} day(s), skipped {skipped} with an existing input.");
+
+    if !failed.is_empty() {
+        eprintln!("Failed to download day(s) {failed:?}.");
+        process::exit(1);
+    }
+}
+
+/// Whether the input of `day` exists, which is reported along with local modifications.
+/// Scaffolding creates an empty input file, which does not count.
+fn is_skipped(day: u8, input_path: &Path) -> bool {
+    if !encryption::is_stored(input_path) {
+        return false;
+    }
+
+    match checksums::verify(input_path) {
+        Ok(checksums::Status::Modified) => println!(
+            "Skipped day {day}, \"{}\" exists and was modified after its download.",
+            input_path.display()
+        ),
+        _ => println!("Skipped day {day}, \"{}\" exists.", input_path.display()),
+    }
+    true
+}
+
+/// Fetches the input directly, so the request identifies the template, and the description with
+/// aoc-cli, which converts it to markdown.
+fn download_day(year: u16, day: u8, token: &str, input_path: &Path) -> Result<(), String> {
+    let response =
+        http::get(&format!("/{year}/day/{day}/input"), token).map_err(|e| e.to_string())?;
+
+    if response.status != 200 {
+        return Err(format!(
+            "the site answered with status {}: {}",
+            response.status,
+            response.body.trim()
+        ));
+    }
+
+    let write_input = || {
+        if let Some(dir) = input_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(input_path, &response.body)?;
+        checksums::record(input_path)
+    };
+
+    write_input().map_err(|e| format!("could not write the input: {e}"))?;
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    encryption::store_download(input_path, "inputs");
+
+    aoc_cli::download_puzzle(day).map_err(|e| format!("failed to call aoc-cli: {e}"))?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Days;
+
+    #[test]
+    fn parses_days() {
+        assert_eq!("5".parse(), Ok(Days::One(5)));
+        assert_eq!("1-10".parse(), Ok(Days::Range(1, 10)));
+        assert_eq!("7-7".parse(), Ok(Days::Range(7, 7)));
+        assert!("0".parse::<Days>().is_err());
+        assert!("26".parse::<Days>().is_err());
+        assert!("10-1".parse::<Days>().is_err());
+        assert!("1-".parse::<Days>().is_err());
+    }
 }
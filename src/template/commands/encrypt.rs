@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process::{self, Command},
+};
+
+use crate::template::{
+    config::{Config, Layout, YEAR_PLACEHOLDER},
+    encryption,
+};
+
+const GITIGNORE: &str = ".gitignore";
+
+/// Encrypts the inputs, and puzzles if enabled, and makes git ignore their plain files. Creates
+/// the key file if there is none. Without `--year`, every year with solutions is encrypted.
+pub fn handle() {
+    let config = Config::get();
+
+    match encryption::create_key() {
+        Ok(true) => println!(
+            "🔑 Created the key file \"{}\". Share it with your team privately, never commit it.",
+            config.key_path().display()
+        ),
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to create the key file: {e}");
+            process::exit(1);
+        }
+    }
+
+    let key = match encryption::load_key() {
+        Ok(Some(key)) => key,
+        Ok(None) => unreachable!("the key file was created above"),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    let configs = match (config.layout, config.year) {
+        (Layout::MultiYear, None) => config
+            .solved_years()
+            .into_iter()
+            .map(|year| config.for_year(year))
+            .collect(),
+        _ => vec![config.clone()],
+    };
+
+    let (mut written, mut unchanged) = (0, 0);
+
+    for config in &configs {
+        for folder in ["inputs", "puzzles"] {
+            if !encryption::is_encrypted(folder) {
+                continue;
+            }
+
+            let Ok(entries) = fs::read_dir(config.data_dir(folder)) else {
+                continue;
+            };
+
+            let mut paths = entries
+                .flatten()
+                .map(|entry| entry.path())
+                // scaffolding creates empty inputs.
+                .filter(|path| {
+                    fs::metadata(path).is_ok_and(|m| m.is_file() && m.len() > 0)
+                        && path
+                            .extension()
+                            .is_some_and(|extension| extension == "txt" || extension == "md")
+                })
+                .collect::<Vec<_>>();
+            paths.sort();
+
+            for path in paths {
+                match encryption::encrypt_file(&key, &path) {
+                    Ok(true) => {
+                        println!("🔒 Encrypted \"{}\"", path.display());
+                        written += 1;
+                    }
+                    Ok(false) => unchanged += 1,
+                    Err(e) => {
+                        eprintln!("Failed to encrypt \"{}\": {e}", path.display());
+                        process::exit(1);
+                    }
+                }
+            }
+        }
+    }
+
+    for (folder, template, extension) in [
+        ("inputs", &config.data.inputs, "txt"),
+        ("puzzles", &config.data.puzzles, "md"),
+    ] {
+        if encryption::is_encrypted(folder) {
+            ignore_plain_files(&configs[0].data_dir(folder), template, extension);
+        }
+    }
+
+    println!("---");
+    println!("🎄 Encrypted {written} file(s), {unchanged} were up to date. Commit the .enc files to share them.");
+}
+
+/// Adds a pattern for the plain files in `dir` to `.gitignore` unless git ignores them already, and
+/// warns about plain files that were committed before. `template` is the configured directory.
+fn ignore_plain_files(dir: &Path, template: &str, extension: &str) {
+    let sample = dir.join(format!("01.{extension}"));
+    let ignored = Command::new("git")
+        .args(["check-ignore", "--quiet", "--no-index"])
+        .arg(&sample)
+        .status();
+
+    // git exits with 1 for paths that are not ignored, and 128 outside of a repository.
+    match ignored.map(|status| status.code()) {
+        Ok(Some(1)) => {
+            let pattern = format!(
+                "/{}/*.{extension}",
+                template
+                    .trim_start_matches("./")
+                    .trim_end_matches('/')
+                    .replace(YEAR_PLACEHOLDER, "*")
+            );
+
+            match append_line(GITIGNORE, &pattern) {
+                Ok(()) => println!("🙈 Added \"{pattern}\" to {GITIGNORE}."),
+                Err(e) => eprintln!("Warning: could not add \"{pattern}\" to {GITIGNORE}: {e}"),
+            }
+        }
+        Ok(Some(0)) => {}
+        _ => return,
+    }
+
+    let tracked = Command::new("git")
+        .args(["ls-files", "--"])
+        .arg(dir)
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default();
+
+    let tracked = tracked
+        .lines()
+        .filter(|path| path.ends_with(&format!(".{extension}")))
+        .collect::<Vec<&str>>();
+
+    if !tracked.is_empty() {
+        eprintln!(
+            "Warning: plain files in \"{}\" were committed before. Remove them from git with `git rm --cached {}`.",
+            dir.display(),
+            tracked.join(" ")
+        );
+    }
+}
+
+fn append_line(path: &str, line: &str) -> io::Result<()> {
+    let contents = fs::read_to_string(path).unwrap_or_default();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        writeln!(file)?;
+    }
+    writeln!(file, "{line}")
+}
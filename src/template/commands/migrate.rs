@@ -0,0 +1,193 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::config::{Config, Layout, CONFIG_FILE};
+
+/// Moves a single-year repository to the multi-year layout: solutions, data files and the README
+/// table of the selected year are renamed, and `layout = "multi-year"` is added to the config.
+pub fn handle(dry_run: bool) {
+    let config = Config::get();
+
+    if config.layout == Layout::MultiYear {
+        println!("This repository already uses the multi-year layout.");
+        return;
+    }
+
+    let Some(year) = config.year else {
+        eprintln!("No year selected. Pass --year <year> or set `year` in {CONFIG_FILE}.");
+        process::exit(1);
+    };
+
+    let contents = fs::read_to_string(CONFIG_FILE).unwrap_or_default();
+    let migrated_contents = migrate_config(&contents, year);
+
+    let migrated = match Config::parse(&migrated_contents) {
+        Ok(migrated) => migrated.for_year(year),
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("Update the settings above in {CONFIG_FILE} to contain {{year}}, then migrate again.");
+            process::exit(1);
+        }
+    };
+
+    let moves = plan_moves(config, &migrated);
+
+    let conflicts = moves
+        .iter()
+        .filter(|(_, to)| to.exists())
+        .map(|(_, to)| to.display().to_string())
+        .collect::<Vec<String>>();
+
+    if !conflicts.is_empty() {
+        eprintln!("Not migrating, these files already exist:");
+        for conflict in conflicts {
+            eprintln!("  {conflict}");
+        }
+        process::exit(1);
+    }
+
+    let (old_marker, new_marker) = (config.readme_marker(), migrated.readme_marker());
+
+    for (from, to) in &moves {
+        println!("Move \"{}\" to \"{}\"", from.display(), to.display());
+    }
+    println!(
+        "Rename the benchmark table marker in \"{}\"",
+        config.readme.path
+    );
+    println!("Set layout = \"multi-year\" in \"{CONFIG_FILE}\"");
+
+    if dry_run {
+        println!("---");
+        println!("🎄 Dry run, nothing was changed.");
+        return;
+    }
+
+    if let Err(e) = apply(
+        &moves,
+        &config.readme.path,
+        &old_marker,
+        &new_marker,
+        &migrated_contents,
+    ) {
+        eprintln!("Failed to migrate: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("🎄 Migrated to the multi-year layout. Type `cargo solve <day> --year {year}` to run a solution.");
+}
+
+/// The files to rename: solutions, and everything in the data directories that change.
+fn plan_moves(config: &Config, migrated: &Config) -> Vec<(PathBuf, PathBuf)> {
+    let mut moves = vec![];
+
+    for day in 1..=25 {
+        let from = PathBuf::from(config.bin_path(day));
+        if from.exists() {
+            moves.push((from, PathBuf::from(migrated.bin_path(day))));
+        }
+    }
+
+    for folder in ["inputs", "examples", "puzzles", "viz"] {
+        let (from_dir, to_dir) = (config.data_dir(folder), migrated.data_dir(folder));
+
+        let Ok(entries) = fs::read_dir(&from_dir) else {
+            continue;
+        };
+
+        if from_dir == to_dir {
+            continue;
+        }
+
+        for entry in entries.flatten() {
+            if entry.path().is_file() {
+                moves.push((entry.path(), to_dir.join(entry.file_name())));
+            }
+        }
+    }
+
+    moves
+}
+
+fn apply(
+    moves: &[(PathBuf, PathBuf)],
+    readme_path: &str,
+    old_marker: &str,
+    new_marker: &str,
+    config_contents: &str,
+) -> std::io::Result<()> {
+    for (from, to) in moves {
+        if let Some(dir) = to.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::rename(from, to)?;
+
+        // leave no empty directories of the old layout behind.
+        if let Some(dir) = from.parent().filter(|dir| *dir != Path::new("./src/bin")) {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+
+    if let Ok(readme) = fs::read_to_string(readme_path) {
+        fs::write(readme_path, readme.replace(old_marker, new_marker))?;
+    }
+
+    fs::write(CONFIG_FILE, config_contents)
+}
+
+/// Adds `layout = "multi-year"` to the root section of a config file, and the year if it is missing.
+fn migrate_config(contents: &str, year: u16) -> String {
+    let mut lines = contents.lines().map(String::from).collect::<Vec<String>>();
+
+    let find_root_key = |lines: &[String], key: &str| {
+        lines
+            .iter()
+            .take_while(|line| !line.trim_start().starts_with('['))
+            .position(|line| {
+                !line.trim_start().starts_with('#')
+                    && line.split_once('=').map(|(k, _)| k.trim()) == Some(key)
+            })
+    };
+
+    let year_line = find_root_key(&lines, "year").unwrap_or_else(|| {
+        lines.insert(0, format!("year = {year}"));
+        0
+    });
+
+    let layout = "layout = \"multi-year\"".to_string();
+    match find_root_key(&lines, "layout") {
+        Some(i) => lines[i] = layout,
+        None => lines.insert(year_line + 1, layout),
+    }
+
+    lines.join("\n") + "\n"
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::migrate_config;
+
+    #[test]
+    fn adds_layout_after_year() {
+        assert_eq!(
+            migrate_config("# config\nyear = 2023\n\n[defaults]\ntime = true\n", 2023),
+            "# config\nyear = 2023\nlayout = \"multi-year\"\n\n[defaults]\ntime = true\n"
+        );
+    }
+
+    #[test]
+    fn replaces_layout_and_adds_year() {
+        assert_eq!(
+            migrate_config("layout = \"single-year\"\n[data]\n", 2022),
+            "year = 2022\nlayout = \"multi-year\"\n[data]\n"
+        );
+        assert_eq!(
+            migrate_config("", 2022),
+            "year = 2022\nlayout = \"multi-year\"\n"
+        );
+    }
+}
@@ -1,5 +1,10 @@
 pub mod all;
 pub mod download;
+pub mod encrypt;
+pub mod migrate;
 pub mod read;
 pub mod scaffold;
+pub mod scale;
+pub mod session;
 pub mod solve;
+pub mod today;
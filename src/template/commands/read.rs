@@ -1,13 +1,15 @@
 use std::process;
 
-use crate::template::aoc_cli;
+use crate::template::{aoc_cli, session};
 
 pub fn handle(day: u8) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
+    session::preflight();
+
     if let Err(e) = aoc_cli::read(day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
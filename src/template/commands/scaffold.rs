@@ -1,9 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
+use crate::template::config::{Config, Layout};
+
 const MODULE_TEMPLATE: &str = r#"pub fn part_one(input: &str) -> Option<u32> {
     None
 }
@@ -37,15 +40,19 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
     OpenOptions::new().write(true).create(true).open(path)
 }
 
 pub fn handle(day: u8) {
     let day_padded = format!("{day:02}");
+    let config = Config::get();
 
-    let input_path = format!("data/inputs/{day_padded}.txt");
-    let example_path = format!("data/examples/{day_padded}.txt");
-    let module_path = format!("src/bin/{day_padded}.rs");
+    let input_path = format!("{}/{day_padded}.txt", config.data_dir("inputs").display());
+    let example_path = format!("{}/{day_padded}.txt", config.data_dir("examples").display());
+    let module_path = format!("src/bin/{}.rs", config.bin_name(day));
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -85,9 +92,14 @@ pub fn handle(day: u8) {
         }
     }
 
+    let year_flag = match (config.layout, config.year) {
+        (Layout::MultiYear, Some(year)) => format!(" --year {year}"),
+        _ => String::new(),
+    };
+
     println!("---");
     println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
+        "🎄 Type `cargo solve {}{}` to run your solution.",
+        &day_padded, year_flag
     );
 }
@@ -0,0 +1,35 @@
+use std::process::{Command, Stdio};
+
+use crate::template::config::Config;
+
+/// Runs a solution with `--scale`, always in release mode since debug timings say little about scaling.
+pub fn handle(day: u8, year: Option<u16>, params: &[String]) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        Config::get().bin_name(day),
+        "--".to_string(),
+        "--scale".to_string(),
+    ];
+
+    if let Some(year) = year {
+        cmd_args.push("--year".to_string());
+        cmd_args.push(year.to_string());
+    }
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
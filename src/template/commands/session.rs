@@ -0,0 +1,72 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    process,
+};
+
+use crate::template::session::{self, Status};
+
+/// Stores a token after checking it against the site, or shows the stored session with `status`.
+/// The token is read from stdin, with a prompt in a terminal, which keeps it out of the shell
+/// history and the process list. A token passed as an argument is still accepted, with a warning.
+pub fn handle(token: Option<String>, status: bool, force: bool) {
+    if status {
+        match session::load() {
+            Ok(session) => session::print_status(&session),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let token = match token.filter(|token| token != "-") {
+        Some(token) => {
+            eprintln!("Warning: the token is now in your shell history, run `cargo session` without it next time.");
+            token
+        }
+        None => read_token(),
+    };
+
+    let token = match session::normalize(&token) {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match session::check(&token) {
+        Status::Valid { user } => println!("Session token belongs to {user}."),
+        Status::Unauthorized if !force => {
+            eprintln!("The site rejected this session token. Pass --force to store it anyway.");
+            process::exit(1);
+        }
+        Status::Unauthorized => eprintln!("Warning: the site rejected this session token."),
+        Status::Unknown(e) => eprintln!("Warning: could not check the session token, {e}"),
+    }
+
+    match session::store(&token) {
+        Ok(path) => println!("🎄 Stored session token in \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Failed to store session token: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn read_token() -> String {
+    let stdin = io::stdin();
+
+    if stdin.is_terminal() {
+        print!("Paste your session token and press Enter: ");
+        let _ = io::stdout().flush();
+    }
+
+    let mut line = String::new();
+    if let Err(e) = stdin.read_line(&mut line) {
+        eprintln!("Failed to read the token from stdin: {e}");
+        process::exit(1);
+    }
+    line
+}
@@ -1,9 +1,23 @@
 use std::process::{Command, Stdio};
 
-pub fn handle(day: u8, release: bool, time: bool, submit_part: Option<u8>) {
-    let day_padded = format!("{day:02}");
-
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day_padded];
+use crate::template::{config::Config, submission::SubmitOptions, viz::VizOptions};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: u8,
+    year: Option<u16>,
+    release: bool,
+    time: bool,
+    explain: bool,
+    viz: Option<VizOptions>,
+    submit: Option<SubmitOptions>,
+    params: &[String],
+) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        Config::get().bin_name(day),
+    ];
 
     if release {
         cmd_args.push("--release".to_string());
@@ -11,15 +25,32 @@ pub fn handle(day: u8, release: bool, time: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if let Some(year) = year {
+        cmd_args.push("--year".to_string());
+        cmd_args.push(year.to_string());
+    }
+
+    if let Some(submit) = submit {
+        cmd_args.extend(submit.to_args());
     }
 
     if time {
         cmd_args.push("--time".to_string());
     }
 
+    if explain {
+        cmd_args.push("--explain".to_string());
+    }
+
+    if let Some(viz) = viz {
+        cmd_args.extend(viz.to_args());
+    }
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
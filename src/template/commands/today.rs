@@ -0,0 +1,408 @@
+use std::{
+    fs,
+    io::{stdout, Write},
+    path::Path,
+    process::{self, Command, Stdio},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{
+    commands::{
+        download::{self, Days},
+        read, scaffold,
+    },
+    config::{Config, Layout, CONFIG_FILE},
+    encryption, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Puzzles unlock at midnight in UTC-5.
+const PUZZLE_UTC_OFFSET: i64 = -5 * 60 * 60;
+const DAY_SECS: i64 = 24 * 60 * 60;
+/// Within this time before an unlock, the upcoming puzzle is today's.
+const WAIT_WINDOW: Duration = Duration::from_secs(60 * 60);
+/// Longer waits are not worth keeping a terminal open for.
+const MAX_WAIT: Duration = Duration::from_secs(24 * 60 * 60);
+/// Time for the site to publish the puzzle after the unlock.
+const UNLOCK_GRACE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Today {
+    Unlocked {
+        year: u16,
+        day: u8,
+    },
+    Locked {
+        year: u16,
+        day: u8,
+        remaining: Duration,
+    },
+    Over {
+        year: u16,
+    },
+}
+
+/// Sets up the current puzzle: waits for its unlock if needed, then scaffolds the day, downloads
+/// the input, shows the description and runs the tests on every change of the solution.
+/// Steps that were already done are skipped, so existing files are never overwritten.
+pub fn handle(watch: bool) {
+    let (year, day, remaining) = match today(unix_now(), |year| Config::get().days(year)) {
+        Today::Unlocked { year, day } => (year, day, None),
+        Today::Locked {
+            year,
+            day,
+            remaining,
+        } => {
+            if remaining > MAX_WAIT {
+                println!(
+                    "Day {day} of {year} unlocks in {}. Come back closer to the unlock.",
+                    format_countdown(remaining)
+                );
+                return;
+            }
+            (year, day, Some(remaining))
+        }
+        Today::Over { year } => {
+            println!(
+                "Advent of Code {year} is over. Use `cargo scaffold <day>` to solve older puzzles."
+            );
+            return;
+        }
+    };
+
+    let config = Config::get();
+
+    // the single-year layout works without a year, aoc-cli then uses the current one.
+    let mismatch = match config.year {
+        Some(selected) => selected != year,
+        None => config.layout == Layout::MultiYear,
+    };
+
+    // fail before the countdown rather than after it.
+    if mismatch {
+        eprintln!(
+            "Today's puzzle is from {year}, but {} is selected. Pass --year {year} or update `year` in {CONFIG_FILE}.",
+            config.year.map_or("no year".into(), |y| y.to_string())
+        );
+        process::exit(1);
+    }
+
+    if let Some(remaining) = remaining {
+        wait_for_unlock(day, remaining);
+    }
+
+    println!("{ANSI_BOLD}Day {day} of {year}{ANSI_RESET}");
+    let bin_path = config.bin_path(day);
+
+    if Path::new(&bin_path).exists() {
+        println!("Skipped scaffolding, \"{bin_path}\" exists.");
+    } else {
+        scaffold::handle(day);
+    }
+
+    let input_path = config.data_dir("inputs").join(format!("{day:02}.txt"));
+
+    // scaffolding creates an empty input file.
+    if encryption::is_stored(&input_path) {
+        println!("Skipped download, \"{}\" exists.", input_path.display());
+    } else {
+        download::handle(Days::One(day), false);
+    }
+
+    read::handle(day);
+
+    if watch {
+        watch_tests(config, day);
+    }
+}
+
+/// The number of unlocked days of `year`, e.g. all of them for past events.
+#[must_use]
+pub fn unlocked_days(year: u16) -> u8 {
+    let days = |year| Config::get().days(year);
+    let (current_year, unlocked) = current_event(unix_now(), days);
+
+    match year.cmp(&current_year) {
+        std::cmp::Ordering::Less => days(year),
+        std::cmp::Ordering::Equal => unlocked,
+        std::cmp::Ordering::Greater => 0,
+    }
+}
+
+/// The most recent event with an unlocked day, which aoc-cli uses without a year.
+#[must_use]
+pub fn latest_event() -> u16 {
+    match current_event(unix_now(), |year| Config::get().days(year)) {
+        (year, 0) => year - 1,
+        (year, _) => year,
+    }
+}
+
+/// The year of the event at unix time `now` and how many of its days are unlocked.
+fn current_event(now: i64, days: impl Fn(u16) -> u8) -> (u16, u8) {
+    match today(now, &days) {
+        Today::Unlocked { year, day } => (year, day),
+        Today::Locked { year, day, .. } => (year, day - 1),
+        Today::Over { year } => (year, days(year)),
+    }
+}
+
+fn unix_now() -> i64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    i64::try_from(now.as_secs()).unwrap()
+}
+
+/// The puzzle of the day at unix time `now`, for events with `days(year)` puzzles.
+fn today(now: i64, days: impl Fn(u16) -> u8) -> Today {
+    let local = now + PUZZLE_UTC_OFFSET;
+    let (year, month, day) = civil_from_days(local.div_euclid(DAY_SECS));
+    let until_midnight = Duration::from_secs((DAY_SECS - local.rem_euclid(DAY_SECS)) as u64);
+    let days = days(year);
+
+    match (month, day) {
+        (12, day) if day < days && until_midnight <= WAIT_WINDOW => Today::Locked {
+            year,
+            day: day + 1,
+            remaining: until_midnight,
+        },
+        (12, day) if day <= days => Today::Unlocked { year, day },
+        (12, _) => Today::Over { year },
+        _ => {
+            let unlock = days_from_civil(year, 12, 1) * DAY_SECS - PUZZLE_UTC_OFFSET;
+            Today::Locked {
+                year,
+                day: 1,
+                remaining: Duration::from_secs((unlock - now) as u64),
+            }
+        }
+    }
+}
+
+/// Days since 1970-01-01 of a date, see <https://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+    let (month, day) = (i64::from(month), i64::from(day));
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date of a number of days since 1970-01-01, the inverse of [`days_from_civil`].
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn civil_from_days(days: i64) -> (u16, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as u16, month as u8, day as u8)
+}
+
+fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, minutes, seconds) = (
+        secs / 86400,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+fn wait_for_unlock(day: u8, remaining: Duration) {
+    let unlock = SystemTime::now() + remaining + UNLOCK_GRACE;
+
+    while let Ok(left) = unlock.duration_since(SystemTime::now()) {
+        print!("\r⏳ Day {day} unlocks in {}", format_countdown(left));
+        let _ = stdout().flush();
+        thread::sleep(left.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🔓 Day {day} is unlocked.            ");
+}
+
+/// Runs the tests of `day` now and whenever its solution or example changes, until interrupted.
+fn watch_tests(config: &Config, day: u8) {
+    let bin_name = config.bin_name(day);
+    let watched = [
+        config.bin_path(day).into(),
+        config.data_dir("examples").join(format!("{day:02}.txt")),
+    ];
+
+    let modified = || {
+        watched
+            .iter()
+            .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .max()
+    };
+
+    println!("---");
+    println!("👀 Running the tests on every change, press Ctrl-C to stop.");
+
+    let mut last_run = None;
+
+    loop {
+        let current = modified();
+
+        if current != last_run {
+            last_run = current;
+
+            let status = Command::new("cargo")
+                .args(["test", "--quiet", "--bin", &bin_name])
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .status();
+
+            if let Err(e) = status {
+                eprintln!("Failed to run the tests: {e}");
+                process::exit(1);
+            }
+
+            println!("👀 Waiting for changes...");
+        }
+
+        thread::sleep(Duration::from_millis(500));
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        civil_from_days, current_event, days_from_civil, format_countdown, today, Today, DAY_SECS,
+    };
+    use crate::template::config::Config;
+    use std::time::Duration;
+
+    fn days(year: u16) -> u8 {
+        Config::default().days(year)
+    }
+
+    /// Unix time of a time of day in UTC-5.
+    fn puzzle_time(year: u16, month: u8, day: u8, hour: i64, minute: i64) -> i64 {
+        days_from_civil(year, month, day) * DAY_SECS + (hour + 5) * 3600 + minute * 60
+    }
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2023, 12, 1), 19692);
+        assert_eq!(civil_from_days(19692), (2023, 12, 1));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn finds_todays_puzzle() {
+        assert_eq!(
+            today(puzzle_time(2023, 12, 5, 0, 0), days),
+            Today::Unlocked { year: 2023, day: 5 }
+        );
+        assert_eq!(
+            today(puzzle_time(2023, 12, 25, 23, 59), days),
+            Today::Unlocked {
+                year: 2023,
+                day: 25
+            }
+        );
+        assert_eq!(
+            today(puzzle_time(2023, 12, 26, 0, 0), days),
+            Today::Over { year: 2023 }
+        );
+    }
+
+    #[test]
+    fn ends_shorter_events_early() {
+        assert_eq!(
+            today(puzzle_time(2025, 12, 12, 23, 30), days),
+            Today::Unlocked {
+                year: 2025,
+                day: 12
+            }
+        );
+        assert_eq!(
+            today(puzzle_time(2025, 12, 13, 0, 0), days),
+            Today::Over { year: 2025 }
+        );
+        assert_eq!(
+            today(puzzle_time(2025, 12, 13, 0, 0), |_| 25),
+            Today::Unlocked {
+                year: 2025,
+                day: 13
+            }
+        );
+    }
+
+    #[test]
+    fn waits_for_upcoming_puzzle() {
+        assert_eq!(
+            today(puzzle_time(2023, 12, 5, 23, 30), days),
+            Today::Locked {
+                year: 2023,
+                day: 6,
+                remaining: Duration::from_secs(30 * 60)
+            }
+        );
+        assert_eq!(
+            today(puzzle_time(2023, 11, 30, 23, 59), days),
+            Today::Locked {
+                year: 2023,
+                day: 1,
+                remaining: Duration::from_secs(60)
+            }
+        );
+        // midnight in UTC is still the previous day in UTC-5.
+        assert_eq!(
+            today(days_from_civil(2023, 12, 1) * DAY_SECS, days),
+            Today::Locked {
+                year: 2023,
+                day: 1,
+                remaining: Duration::from_secs(5 * 3600)
+            }
+        );
+    }
+
+    #[test]
+    fn counts_unlocked_days() {
+        assert_eq!(
+            current_event(puzzle_time(2023, 10, 1, 12, 0), days),
+            (2023, 0)
+        );
+        assert_eq!(
+            current_event(puzzle_time(2023, 12, 5, 23, 30), days),
+            (2023, 5)
+        );
+        assert_eq!(
+            current_event(puzzle_time(2024, 1, 1, 0, 0), days),
+            (2024, 0)
+        );
+        assert_eq!(
+            current_event(puzzle_time(2023, 12, 31, 0, 0), days),
+            (2023, 25)
+        );
+        assert_eq!(
+            current_event(puzzle_time(2025, 12, 31, 0, 0), days),
+            (2025, 12)
+        );
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(
+            format_countdown(Duration::from_secs(3 * 86400 + 3723)),
+            "3d 01:02:03"
+        );
+    }
+}
@@ -0,0 +1,805 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+/// Every setting is optional and falls back to the template defaults. Flags passed on the command-line
+/// take precedence over the file. The file is parsed with the `toml` crate, all settings are:
+///
+/// ```toml
+/// year = 2023
+/// days = 25
+/// layout = "single-year"
+/// session_file = "~/.adventofcode.session"
+/// url = "https://adventofcode.com"
+///
+/// [data]
+/// inputs = "data/inputs"
+/// examples = "data/examples"
+/// puzzles = "data/puzzles"
+/// viz = "data/viz"
+///
+/// [bench]
+/// duration_ms = 1000
+/// min_samples = 10
+/// max_samples = 10000
+///
+/// [readme]
+/// path = "README.md"
+/// marker = "<!--- benchmarking table --->"
+///
+/// [defaults]
+/// release = false
+/// time = false
+///
+/// [encryption]
+/// key_file = ".aoc.key"
+/// puzzles = false
+/// ```
+use serde::Deserialize;
+use std::{
+    env,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+    time::Duration,
+};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// The first Advent of Code took place in 2015.
+const FIRST_YEAR: i64 = 2015;
+/// Events have 12 instead of 25 puzzles since 2025.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// Placeholder for the selected year in data paths and the README marker.
+pub const YEAR_PLACEHOLDER: &str = "{year}";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The event passed to aoc-cli. Without a year, aoc-cli picks the current or most recent event.
+    /// `--year` and, in the multi-year layout, the name of the running solution take precedence.
+    pub year: Option<u16>,
+    /// The number of puzzles of the selected event, which otherwise depends on its year, see
+    /// [`Config::days`].
+    pub days: Option<u8>,
+    pub layout: Layout,
+    /// The session cookie file passed to aoc-cli, which defaults to `~/.adventofcode.session`.
+    pub session_file: Option<String>,
+    /// The site checked for session tokens, e.g. a local mock for testing.
+    pub url: String,
+    pub data: Data,
+    pub bench: Bench,
+    pub readme: Readme,
+    pub defaults: Defaults,
+    pub encryption: Encryption,
+}
+
+/// How solutions and their data are organized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// One year per repository: `src/bin/05.rs` and `data/inputs/05.txt`.
+    #[default]
+    SingleYear,
+    /// Years side by side: `src/bin/2023-05.rs` and `data/2023/inputs/05.txt`.
+    MultiYear,
+}
+
+/// Directories of the files belonging to each day, which may contain `{year}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Data {
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    pub viz: String,
+}
+
+/// How long solutions are benched for with `--time`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bench {
+    pub duration: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+/// Where `cargo time` writes the benchmark table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Readme {
+    pub path: String,
+    pub marker: String,
+}
+
+/// Defaults of the `--release` and `--time` flags, which can be overridden with `--no-release` and
+/// `--no-time`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Defaults {
+    pub release: bool,
+    pub time: bool,
+}
+
+/// Where the key for encrypted data files is kept, see `template::encryption`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Encryption {
+    pub key_file: String,
+    /// Encrypt puzzle descriptions as well as inputs.
+    pub puzzles: bool,
+}
+
+impl Default for Encryption {
+    fn default() -> Self {
+        Self {
+            key_file: ".aoc.key".into(),
+            puzzles: false,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            days: None,
+            layout: Layout::SingleYear,
+            session_file: None,
+            url: "https://adventofcode.com".into(),
+            data: Data::for_layout(Layout::SingleYear),
+            bench: Bench {
+                duration: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10000,
+            },
+            readme: Readme::for_layout(Layout::SingleYear),
+            defaults: Defaults::default(),
+            encryption: Encryption::default(),
+        }
+    }
+}
+
+impl Layout {
+    const NAMES: [(&'static str, Layout); 2] = [
+        ("single-year", Layout::SingleYear),
+        ("multi-year", Layout::MultiYear),
+    ];
+}
+
+impl Data {
+    fn for_layout(layout: Layout) -> Self {
+        let root = match layout {
+            Layout::SingleYear => "data".to_string(),
+            Layout::MultiYear => format!("data/{YEAR_PLACEHOLDER}"),
+        };
+
+        Self {
+            inputs: format!("{root}/inputs"),
+            examples: format!("{root}/examples"),
+            puzzles: format!("{root}/puzzles"),
+            viz: format!("{root}/viz"),
+        }
+    }
+}
+
+impl Readme {
+    fn for_layout(layout: Layout) -> Self {
+        let marker = match layout {
+            Layout::SingleYear => "<!--- benchmarking table --->".to_string(),
+            Layout::MultiYear => format!("<!--- benchmarking table {YEAR_PLACEHOLDER} --->"),
+        };
+
+        Self {
+            path: "README.md".into(),
+            marker,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    /// The file is not valid TOML, or has an unknown key or a value of the wrong type.
+    Parse(toml::de::Error),
+    /// One message per setting with an invalid value.
+    Invalid(Vec<String>),
+    /// A command-line flag overriding a setting is invalid.
+    Argument(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "could not read {CONFIG_FILE}: {e}"),
+            Error::Parse(e) => write!(f, "invalid {CONFIG_FILE}: {e}"),
+            Error::Invalid(errors) => {
+                write!(f, "invalid {CONFIG_FILE}:")?;
+                for error in errors {
+                    write!(f, "\n  - {error}")?;
+                }
+                Ok(())
+            }
+            Error::Argument(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// The settings as written in the file, before they are validated and applied to the defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct File {
+    year: Option<i64>,
+    days: Option<i64>,
+    layout: Option<String>,
+    session_file: Option<String>,
+    url: Option<String>,
+    data: DataFile,
+    bench: BenchFile,
+    readme: ReadmeFile,
+    defaults: DefaultsFile,
+    encryption: EncryptionFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DataFile {
+    inputs: Option<String>,
+    examples: Option<String>,
+    puzzles: Option<String>,
+    viz: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct BenchFile {
+    duration_ms: Option<i64>,
+    min_samples: Option<i64>,
+    max_samples: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ReadmeFile {
+    path: Option<String>,
+    marker: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DefaultsFile {
+    release: Option<bool>,
+    time: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct EncryptionFile {
+    key_file: Option<String>,
+    puzzles: Option<bool>,
+}
+
+impl Config {
+    /// The configuration of the current project with the year selected for the current process,
+    /// loaded once per process. Exits with the validation errors if the config file is invalid.
+    pub fn get() -> &'static Config {
+        static CONFIG: OnceLock<Config> = OnceLock::new();
+
+        CONFIG.get_or_init(|| {
+            let path = env::current_dir().unwrap().join(CONFIG_FILE);
+            let args: Vec<String> = env::args().collect();
+
+            match Self::load(&path).and_then(|config| config.select_year(&args)) {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            }
+        })
+    }
+
+    /// Reads and validates a config file. A missing file yields the defaults.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parses and validates the contents of a config file. Syntax errors stop at the first one,
+    /// invalid values are all collected.
+    pub fn parse(contents: &str) -> Result<Self, Error> {
+        let file: File = toml::from_str(contents).map_err(Error::Parse)?;
+        let mut config = Self::default();
+
+        let mut errors = config.apply(file);
+        errors.extend(config.validate());
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(Error::Invalid(errors))
+        }
+    }
+
+    /// Applies `--year <year>` in `args` or, in the multi-year layout, the year in the name of the
+    /// running solution, e.g. `2023-05` or `2023_05-<hash>` for its tests.
+    pub fn select_year(mut self, args: &[String]) -> Result<Self, Error> {
+        let flag = args
+            .iter()
+            .position(|arg| arg == "--year")
+            .map(|i| {
+                let value = args.get(i + 1).map_or("", String::as_str);
+                value
+                    .parse()
+                    .map_err(|_| format!("must be a year, found \"{value}\""))
+                    .and_then(parse_year)
+                    .map_err(|e| Error::Argument(format!("--year {e}")))
+            })
+            .transpose()?;
+
+        let binary = match self.layout {
+            Layout::SingleYear => None,
+            Layout::MultiYear => args.first().and_then(|path| year_of_binary(path)),
+        };
+
+        self.year = flag.or(binary).or(self.year);
+        Ok(self)
+    }
+
+    /// The number of puzzles of the event in `year`: the `days` setting, or else 25 until 2024 and
+    /// 12 since 2025.
+    #[must_use]
+    pub fn days(&self, year: u16) -> u8 {
+        self.days
+            .unwrap_or(if year < FIRST_SHORT_YEAR { 25 } else { 12 })
+    }
+
+    /// A copy of the config with `year` selected.
+    #[must_use]
+    pub fn for_year(&self, year: u16) -> Self {
+        Self {
+            year: Some(year),
+            ..self.clone()
+        }
+    }
+
+    /// Replaces `{year}` in `template` with the selected year.
+    /// Exits with a hint if the template needs a year and none is selected.
+    #[must_use]
+    pub fn expand(&self, template: &str) -> String {
+        if !template.contains(YEAR_PLACEHOLDER) {
+            return template.into();
+        }
+
+        let Some(year) = self.year else {
+            eprintln!("No year selected. Pass --year <year> or set `year` in {CONFIG_FILE}.");
+            process::exit(1);
+        };
+
+        template.replace(YEAR_PLACEHOLDER, &year.to_string())
+    }
+
+    /// The directory of a data folder like `"inputs"` or `"examples"` for the selected year.
+    /// Folders without a setting live in `data/`.
+    #[must_use]
+    pub fn data_dir(&self, folder: &str) -> PathBuf {
+        let template = match folder {
+            "inputs" => &self.data.inputs,
+            "examples" => &self.data.examples,
+            "puzzles" => &self.data.puzzles,
+            "viz" => &self.data.viz,
+            _ => return Path::new("data").join(folder),
+        };
+
+        PathBuf::from(self.expand(template))
+    }
+
+    /// The name of the binary solving `day` in the selected year.
+    #[must_use]
+    pub fn bin_name(&self, day: u8) -> String {
+        match self.layout {
+            Layout::SingleYear => format!("{day:02}"),
+            Layout::MultiYear => self.expand(&format!("{YEAR_PLACEHOLDER}-{day:02}")),
+        }
+    }
+
+    /// The source file of the binary solving `day` in the selected year.
+    #[must_use]
+    pub fn bin_path(&self, day: u8) -> String {
+        format!("./src/bin/{}.rs", self.bin_name(day))
+    }
+
+    /// The years with at least one solution in the multi-year layout, in ascending order.
+    #[must_use]
+    pub fn solved_years(&self) -> Vec<u16> {
+        let Ok(entries) = fs::read_dir("src/bin") else {
+            return vec![];
+        };
+
+        let mut years = entries
+            .filter_map(|entry| year_of_binary(entry.ok()?.path().to_str()?))
+            .collect::<Vec<u16>>();
+        years.sort_unstable();
+        years.dedup();
+        years
+    }
+
+    /// The README marker of the selected year.
+    #[must_use]
+    pub fn readme_marker(&self) -> String {
+        self.expand(&self.readme.marker)
+    }
+
+    /// The session file with a leading `~` expanded to the home directory.
+    #[must_use]
+    pub fn session_path(&self) -> Option<PathBuf> {
+        self.session_file.as_deref().map(expand_home)
+    }
+
+    /// The encryption key file with a leading `~` expanded to the home directory.
+    #[must_use]
+    pub fn key_path(&self) -> PathBuf {
+        expand_home(&self.encryption.key_file)
+    }
+
+    /// Applies the settings of `file`, returning the values that are out of range.
+    fn apply(&mut self, file: File) -> Vec<String> {
+        let mut errors = vec![];
+
+        if let Some(year) = file.year {
+            match parse_year(year) {
+                Ok(year) => self.year = Some(year),
+                Err(e) => errors.push(format!("\"year\" {e}")),
+            }
+        }
+
+        if let Some(days) = file.days {
+            match u8::try_from(days)
+                .ok()
+                .filter(|days| (1..=25).contains(days))
+            {
+                Some(days) => self.days = Some(days),
+                None => errors.push(format!("\"days\" must be from 1 to 25, found {days}")),
+            }
+        }
+
+        if let Some(value) = file.layout {
+            match Layout::NAMES.into_iter().find(|(name, _)| *name == value) {
+                // explicit paths below take precedence over the layout's defaults.
+                Some((_, layout)) => {
+                    self.layout = layout;
+                    self.data = Data::for_layout(layout);
+                    self.readme.marker = Readme::for_layout(layout).marker;
+                }
+                None => errors.push(format!(
+                    "\"layout\" must be \"single-year\" or \"multi-year\", found \"{value}\""
+                )),
+            }
+        }
+
+        self.session_file = file.session_file;
+        replace(&mut self.url, file.url);
+
+        replace(&mut self.data.inputs, file.data.inputs);
+        replace(&mut self.data.examples, file.data.examples);
+        replace(&mut self.data.puzzles, file.data.puzzles);
+        replace(&mut self.data.viz, file.data.viz);
+
+        let [duration_ms, min_samples, max_samples] = [
+            ("bench.duration_ms", file.bench.duration_ms),
+            ("bench.min_samples", file.bench.min_samples),
+            ("bench.max_samples", file.bench.max_samples),
+        ]
+        .map(|(name, value)| {
+            positive(name, value).unwrap_or_else(|e| {
+                errors.push(e);
+                None
+            })
+        });
+        replace(
+            &mut self.bench.duration,
+            duration_ms.map(Duration::from_millis),
+        );
+        replace(&mut self.bench.min_samples, min_samples.map(u128::from));
+        replace(&mut self.bench.max_samples, max_samples.map(u128::from));
+
+        replace(&mut self.readme.path, file.readme.path);
+        replace(&mut self.readme.marker, file.readme.marker);
+        replace(&mut self.defaults.release, file.defaults.release);
+        replace(&mut self.defaults.time, file.defaults.time);
+        replace(&mut self.encryption.key_file, file.encryption.key_file);
+        replace(&mut self.encryption.puzzles, file.encryption.puzzles);
+
+        errors
+    }
+
+    /// Checks settings that depend on each other or must not be empty.
+    fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+
+        let mut paths = vec![
+            ("data.inputs", &self.data.inputs),
+            ("data.examples", &self.data.examples),
+            ("data.puzzles", &self.data.puzzles),
+            ("data.viz", &self.data.viz),
+            ("readme.path", &self.readme.path),
+            ("encryption.key_file", &self.encryption.key_file),
+        ];
+
+        if let Some(session_file) = &self.session_file {
+            paths.push(("session_file", session_file));
+        }
+
+        for (name, path) in paths {
+            if path.trim().is_empty() {
+                errors.push(format!("\"{name}\" must not be empty"));
+            }
+        }
+
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            errors.push(format!(
+                "\"url\" must start with http:// or https://, found \"{}\"",
+                self.url
+            ));
+        }
+
+        if self.readme.marker.trim().is_empty() || self.readme.marker.contains('\n') {
+            errors.push("\"readme.marker\" must be a single, non-empty line".into());
+        }
+
+        if self.layout == Layout::MultiYear {
+            let templates = [
+                ("data.inputs", &self.data.inputs),
+                ("data.examples", &self.data.examples),
+                ("data.puzzles", &self.data.puzzles),
+                ("data.viz", &self.data.viz),
+                ("readme.marker", &self.readme.marker),
+            ];
+
+            for (name, template) in templates {
+                if !template.contains(YEAR_PLACEHOLDER) {
+                    errors.push(format!(
+                        "\"{name}\" must contain {YEAR_PLACEHOLDER} in the multi-year layout"
+                    ));
+                }
+            }
+        }
+
+        if self.bench.min_samples > self.bench.max_samples {
+            errors.push(format!(
+                "\"bench.min_samples\" ({}) must not exceed \"bench.max_samples\" ({})",
+                self.bench.min_samples, self.bench.max_samples
+            ));
+        }
+
+        errors
+    }
+}
+
+/// Overwrites `setting` with `value` if the file sets it.
+fn replace<T>(setting: &mut T, value: Option<T>) {
+    if let Some(value) = value {
+        *setting = value;
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Checks that a setting like `bench.duration_ms`, if it is set, is positive.
+fn positive(name: &str, value: Option<i64>) -> Result<Option<u64>, String> {
+    value
+        .map(|value| {
+            u64::try_from(value)
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| format!("\"{name}\" must be positive, found {value}"))
+        })
+        .transpose()
+}
+
+/// Checks that an event took place in `year`.
+fn parse_year(year: i64) -> Result<u16, String> {
+    u16::try_from(year)
+        .ok()
+        .filter(|&y| i64::from(y) >= FIRST_YEAR)
+        .ok_or_else(|| format!("must be {FIRST_YEAR} or later, found {year}"))
+}
+
+/// The year in the file name of a multi-year solution binary, like `2023-05`, or of its tests,
+/// like `2023_05-<hash>`.
+fn year_of_binary(path: &str) -> Option<u16> {
+    let name = Path::new(path).file_stem()?.to_str()?;
+    let (year, rest) = name.split_at_checked(4)?;
+    let rest = rest.strip_prefix(['-', '_'])?;
+    let (day, hash) = rest.split_at_checked(2)?;
+
+    let is_number = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(year) || !is_number(day) || !(hash.is_empty() || hash.starts_with('-')) {
+        return None;
+    }
+
+    year.parse().ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{year_of_binary, Config, Error, Layout};
+    use std::time::Duration;
+
+    fn errors(contents: &str) -> Vec<String> {
+        match Config::parse(contents) {
+            Err(Error::Invalid(errors)) => errors,
+            result => panic!("expected validation errors, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            r#"
+            # comment
+            year = 2023
+            days = 12
+            session_file = "~/.aoc # session" # comment
+
+            [data]
+            inputs = "inputs/2023"
+
+            [bench]
+            duration_ms = 2_000
+            max_samples = 100
+
+            [readme]
+            marker = "<!-- \"bench\" -->"
+
+            [defaults]
+            time = true
+
+            [encryption]
+            key_file = "~/keys/aoc"
+            puzzles = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.days(2023), 12);
+        assert_eq!(config.session_file.as_deref(), Some("~/.aoc # session"));
+        assert_eq!(config.data.inputs, "inputs/2023");
+        assert_eq!(config.data.examples, "data/examples");
+        assert_eq!(config.bench.duration, Duration::from_secs(2));
+        assert_eq!(
+            (config.bench.min_samples, config.bench.max_samples),
+            (10, 100)
+        );
+        assert_eq!(config.readme.marker, "<!-- \"bench\" -->");
+        assert!(config.defaults.time && !config.defaults.release);
+        assert_eq!(config.encryption.key_file, "~/keys/aoc");
+        assert!(config.encryption.puzzles);
+    }
+
+    #[test]
+    fn defaults_without_settings() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert_eq!(
+            Config::parse("[data]\n# nothing\n").unwrap(),
+            Config::default()
+        );
+    }
+
+    #[test]
+    fn reports_all_invalid_values() {
+        let errors = errors(
+            r#"
+            year = 2014
+            days = 26
+            layout = "multi"
+
+            [bench]
+            min_samples = 50
+            max_samples = 20
+            duration_ms = 0
+
+            [readme]
+            path = ""
+            marker = "\n"
+            "#,
+        );
+
+        assert_eq!(
+            errors,
+            vec![
+                "\"year\" must be 2015 or later, found 2014",
+                "\"days\" must be from 1 to 25, found 26",
+                "\"layout\" must be \"single-year\" or \"multi-year\", found \"multi\"",
+                "\"bench.duration_ms\" must be positive, found 0",
+                "\"readme.path\" must not be empty",
+                "\"readme.marker\" must be a single, non-empty line",
+                "\"bench.min_samples\" (50) must not exceed \"bench.max_samples\" (20)",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        for contents in [
+            "yaer = 2023",
+            "[paths]\ninputs = \"data\"",
+            "year = \"2023\"",
+        ] {
+            assert!(
+                matches!(Config::parse(contents), Err(Error::Parse(_))),
+                "{contents}"
+            );
+        }
+    }
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn uses_year_aware_paths() {
+        let config = Config::parse("layout = \"multi-year\"\n[data]\nviz = \"viz/{year}\"")
+            .unwrap()
+            .for_year(2022);
+
+        assert_eq!(config.layout, Layout::MultiYear);
+        assert_eq!(config.bin_name(5), "2022-05");
+        assert_eq!(config.bin_path(5), "./src/bin/2022-05.rs");
+        assert_eq!(config.data_dir("inputs").to_str(), Some("data/2022/inputs"));
+        assert_eq!(config.data_dir("viz").to_str(), Some("viz/2022"));
+        assert_eq!(config.readme_marker(), "<!--- benchmarking table 2022 --->");
+
+        let config = Config::default();
+        assert_eq!((config.days(2024), config.days(2025)), (25, 12));
+        assert_eq!(config.bin_name(5), "05");
+        assert_eq!(config.data_dir("examples").to_str(), Some("data/examples"));
+        assert_eq!(config.readme_marker(), "<!--- benchmarking table --->");
+    }
+
+    #[test]
+    fn requires_year_placeholders_in_multi_year_layout() {
+        assert_eq!(
+            errors("layout = \"multi-year\"\n[data]\ninputs = \"data/inputs\""),
+            vec!["\"data.inputs\" must contain {year} in the multi-year layout"]
+        );
+    }
+
+    #[test]
+    fn selects_year() {
+        let single = Config::parse("year = 2023").unwrap();
+        let multi = Config::parse("year = 2023\nlayout = \"multi-year\"").unwrap();
+
+        let year = |config: &Config, s: &str| config.clone().select_year(&args(s)).unwrap().year;
+        assert_eq!(
+            year(&single, "target/debug/advent_of_code solve 5"),
+            Some(2023)
+        );
+        assert_eq!(year(&single, "target/debug/05 --year 2021"), Some(2021));
+        assert_eq!(year(&single, "target/debug/2022-05"), Some(2023));
+        assert_eq!(year(&multi, "target/debug/2022-05"), Some(2022));
+        assert_eq!(year(&multi, "target/debug/deps/2022_05-1f2e3d"), Some(2022));
+        assert_eq!(year(&multi, "target/debug/2022-05 --year 2021"), Some(2021));
+
+        assert!(single.clone().select_year(&args("05 --year 2014")).is_err());
+        assert!(single.clone().select_year(&args("05 --year")).is_err());
+    }
+
+    #[test]
+    fn parses_binary_names() {
+        assert_eq!(year_of_binary("target/release/2023-05"), Some(2023));
+        assert_eq!(year_of_binary("2023-05.exe"), Some(2023));
+        assert_eq!(year_of_binary("05"), None);
+        assert_eq!(year_of_binary("advent_of_code"), None);
+        assert_eq!(year_of_binary("2023-5"), None);
+        assert_eq!(year_of_binary("2023-051"), None);
+    }
+}
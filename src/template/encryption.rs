@@ -0,0 +1,326 @@
+/// Encrypted copies of data files that should not be published, so they can be committed.
+/// A file like `data/inputs/01.txt` gets an encrypted copy `data/inputs/01.txt.enc`, written with
+/// the key in the key file of the project config. Reads fall back to the encrypted copy when the
+/// plain file is missing or empty, so solutions run on a fresh checkout as long as the key file is
+/// present.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+use crate::template::{config::Config, session};
+
+/// Identifies the format of encrypted files, followed by the nonce and the ciphertext.
+const MAGIC: &[u8] = b"aoc-enc1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum Error {
+    MissingKey(PathBuf),
+    InvalidKey(PathBuf),
+    /// The file was encrypted with another key or modified.
+    Decrypt(PathBuf),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingKey(path) => write!(
+                f,
+                "no encryption key found in \"{}\". Ask your team for it or run `cargo encrypt` to create one.",
+                path.display()
+            ),
+            Error::InvalidKey(path) => write!(
+                f,
+                "\"{}\" does not contain a key of 64 hexadecimal characters.",
+                path.display()
+            ),
+            Error::Decrypt(path) => write!(
+                f,
+                "could not decrypt \"{}\", it was encrypted with another key or is damaged.",
+                path.display()
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// The encrypted copy of `path`.
+#[must_use]
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".enc");
+    path.with_file_name(name)
+}
+
+/// Whether `path` or its encrypted copy has contents.
+#[must_use]
+pub fn is_stored(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0) || encrypted_path(path).exists()
+}
+
+/// Reads `path`, or decrypts its encrypted copy if the file is missing or empty like the ones
+/// created by `cargo scaffold`.
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    read_or_decrypt(path, decrypt_file)
+}
+
+fn read_or_decrypt(
+    path: &Path,
+    decrypt_copy: impl FnOnce(&Path) -> Result<Vec<u8>, Error>,
+) -> io::Result<String> {
+    let stored = match fs::read_to_string(path) {
+        Ok(contents) if !contents.is_empty() => return Ok(contents),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        stored => stored,
+    };
+
+    if !encrypted_path(path).exists() {
+        return stored;
+    }
+
+    let contents = decrypt_copy(path).map_err(|e| io::Error::other(e.to_string()))?;
+    String::from_utf8(contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// The key of the project, or `None` if there is no key file.
+pub fn load_key() -> Result<Option<Key>, Error> {
+    let path = Config::get().key_path();
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => parse_key(&contents)
+            .map(Some)
+            .ok_or(Error::InvalidKey(path)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Creates a key file readable only by the current user, unless one exists.
+/// Returns whether a key was created.
+pub fn create_key() -> Result<bool, Error> {
+    let path = Config::get().key_path();
+
+    if path.exists() {
+        return Ok(false);
+    }
+
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    session::write_private(&path, &format_key(&key))?;
+    Ok(true)
+}
+
+fn parse_key(hex: &str) -> Option<Key> {
+    let hex = hex.trim();
+
+    if hex.len() != 64 {
+        return None;
+    }
+
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    Some(*Key::from_slice(&bytes))
+}
+
+fn format_key(key: &Key) -> String {
+    key.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Encrypts `contents` of the file `name`. The name is authenticated as well, so encrypted
+/// files cannot be swapped.
+fn encrypt(key: &Key, name: &str, contents: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let payload = Payload {
+        msg: contents,
+        aad: name.as_bytes(),
+    };
+
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, payload)
+        .expect("encrypting in memory does not fail");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+fn decrypt(key: &Key, name: &str, data: &[u8]) -> Option<Vec<u8>> {
+    let data = data.strip_prefix(MAGIC)?;
+
+    if data.len() < NONCE_LEN {
+        return None;
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let payload = Payload {
+        msg: ciphertext,
+        aad: name.as_bytes(),
+    };
+
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), payload)
+        .ok()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into()
+}
+
+/// Decrypts the encrypted copy of `path`.
+pub fn decrypt_file(path: &Path) -> Result<Vec<u8>, Error> {
+    let key = load_key()?.ok_or_else(|| Error::MissingKey(Config::get().key_path()))?;
+    let encrypted = encrypted_path(path);
+
+    decrypt(&key, &file_name(path), &fs::read(&encrypted)?).ok_or(Error::Decrypt(encrypted))
+}
+
+/// Writes the encrypted copy of `path`. An unchanged copy is kept, so re-encrypting does not
+/// produce changes to commit. Returns whether the copy was written.
+pub fn encrypt_file(key: &Key, path: &Path) -> Result<bool, Error> {
+    let contents = fs::read(path)?;
+    let (name, encrypted) = (file_name(path), encrypted_path(path));
+
+    let unchanged = fs::read(&encrypted)
+        .ok()
+        .and_then(|data| decrypt(key, &name, &data))
+        .is_some_and(|existing| existing == contents);
+
+    if unchanged {
+        return Ok(false);
+    }
+
+    fs::write(encrypted, encrypt(key, &name, &contents))?;
+    Ok(true)
+}
+
+/// Whether files of a data folder like `"inputs"` are encrypted.
+#[must_use]
+pub fn is_encrypted(folder: &str) -> bool {
+    match folder {
+        "inputs" => true,
+        "puzzles" => Config::get().encryption.puzzles,
+        _ => false,
+    }
+}
+
+/// Encrypts a downloaded file of `folder` if the project has a key. Failures are only warned
+/// about, as the download itself succeeded.
+pub fn store_download(path: &Path, folder: &str) {
+    if !is_encrypted(folder) {
+        return;
+    }
+
+    let result = load_key().and_then(|key| match key {
+        Some(key) => encrypt_file(&key, path),
+        None => Ok(false),
+    });
+
+    match result {
+        Ok(true) => println!(
+            "🔒 Encrypted \"{}\" to \"{}\".",
+            path.display(),
+            encrypted_path(path).display()
+        ),
+        Ok(false) => {}
+        Err(e) => eprintln!("Warning: could not encrypt \"{}\": {e}", path.display()),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        decrypt, encrypt, encrypted_path, file_name, format_key, parse_key, read_or_decrypt,
+    };
+    use chacha20poly1305::{
+        aead::{KeyInit, OsRng},
+        ChaCha20Poly1305,
+    };
+    use std::{fs, path::Path};
+
+    #[test]
+    fn roundtrips_contents() {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let encrypted = encrypt(&key, "01.txt", b"1\n2\n");
+
+        assert_eq!(
+            decrypt(&key, "01.txt", &encrypted),
+            Some(b"1\n2\n".to_vec())
+        );
+        assert_ne!(encrypt(&key, "01.txt", b"1\n2\n"), encrypted);
+    }
+
+    #[test]
+    fn rejects_other_keys_and_names() {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let other = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let encrypted = encrypt(&key, "01.txt", b"input");
+
+        assert_eq!(decrypt(&other, "01.txt", &encrypted), None);
+        assert_eq!(decrypt(&key, "02.txt", &encrypted), None);
+        assert_eq!(decrypt(&key, "01.txt", b"input"), None);
+        assert_eq!(decrypt(&key, "01.txt", &encrypted[..10]), None);
+    }
+
+    #[test]
+    fn parses_keys() {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let hex = format_key(&key);
+
+        assert_eq!(hex.len(), 64);
+        assert_eq!(parse_key(&format!("{hex}\n")), Some(key));
+        assert_eq!(parse_key(&hex[..62]), None);
+        assert_eq!(parse_key(&"zz".repeat(32)), None);
+    }
+
+    #[test]
+    fn names_encrypted_copies() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01.txt")),
+            Path::new("data/inputs/01.txt.enc")
+        );
+    }
+
+    #[test]
+    fn reads_encrypted_copies_of_missing_and_empty_files() {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let dir = std::env::temp_dir().join(format!("aoc-encryption-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("01.txt");
+        let read = |path: &Path| {
+            read_or_decrypt(path, |path| {
+                let data = fs::read(encrypted_path(path))?;
+                Ok(decrypt(&key, &file_name(path), &data).unwrap())
+            })
+            .unwrap()
+        };
+
+        fs::write(encrypted_path(&file), encrypt(&key, "01.txt", b"1\n2\n")).unwrap();
+        let missing = read(&file);
+        fs::write(&file, "").unwrap();
+        let empty = read(&file);
+        fs::write(&file, "3\n").unwrap();
+        let plain = read(&file);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(missing, "1\n2\n");
+        assert_eq!(empty, "1\n2\n");
+        assert_eq!(plain, "3\n");
+    }
+}
@@ -0,0 +1,214 @@
+/// Checks that run before an answer is submitted, so attempts are not spent on answers that are
+/// obviously wrong. Every check that fails returns the reason.
+use std::{
+    fs,
+    io::{stdin, stdout, Write},
+    process::Command,
+};
+
+use crate::template::config::Config;
+
+/// Answers this close to an integer limit, relative to the limit, most likely overflowed.
+const OVERFLOW_MARGIN: i128 = 1000;
+
+const LIMITS: [(&str, i128); 6] = [
+    ("i32::MAX", i32::MAX as i128),
+    ("u32::MAX", u32::MAX as i128),
+    ("i64::MAX", i64::MAX as i128),
+    ("u64::MAX", u64::MAX as i128),
+    ("i32::MIN", i32::MIN as i128),
+    ("i64::MIN", i64::MIN as i128),
+];
+
+/// Runs every check on `answer` for `part` of `day`, the example tests last as they are slowest.
+#[must_use]
+pub fn check(day: u8, part: u8, answer: &str) -> Vec<String> {
+    let config = Config::get();
+    let source = fs::read_to_string(config.bin_path(day)).unwrap_or_default();
+
+    let mut problems = vec![];
+    problems.extend(check_empty(answer));
+    problems.extend(check_overflow(answer));
+    problems.extend(check_example(answer, &example_answers(&source, part)));
+
+    if problems.is_empty() {
+        problems.extend(run_tests(&config.bin_name(day)));
+    }
+
+    problems
+}
+
+fn check_empty(answer: &str) -> Option<String> {
+    match answer.trim() {
+        "" => Some("the answer is empty.".into()),
+        "0" => Some("the answer is 0.".into()),
+        _ => None,
+    }
+}
+
+fn check_overflow(answer: &str) -> Option<String> {
+    let value = answer.trim().parse::<i128>().ok()?;
+
+    LIMITS.iter().find_map(|(name, limit)| {
+        ((value - limit).abs() <= limit.abs() / OVERFLOW_MARGIN).then(|| {
+            format!("the answer {value} is close to {name} ({limit}), did a calculation overflow?")
+        })
+    })
+}
+
+fn check_example(answer: &str, examples: &[String]) -> Option<String> {
+    examples.contains(&answer.trim().to_string()).then(|| {
+        format!(
+            "the answer {answer} is the expected answer of an example, is the example file used?"
+        )
+    })
+}
+
+/// The expected answers in the test `test_part_one` or `test_part_two` of `part`, e.g. `142` in
+/// `assert_eq!(result, Some(142))`. This reads the source text, so only that exact form is found:
+/// answers of other tests, answers in variables, and other assertions are missed.
+fn example_answers(source: &str, part: u8) -> Vec<String> {
+    const ASSERTION: &str = "assert_eq!(result, Some(";
+
+    let test_name = match part {
+        1 => "fn test_part_one(",
+        _ => "fn test_part_two(",
+    };
+
+    source
+        .split("#[test]")
+        .filter(|test| test.contains(test_name))
+        .flat_map(|test| {
+            test.match_indices(ASSERTION)
+                .map(|(i, _)| &test[i + ASSERTION.len()..])
+        })
+        .filter_map(|assertion| parse_literal(&assertion[..assertion.find("));")?]))
+        .collect()
+}
+
+/// The value of an integer or string literal, e.g. `82_000_210u64` or `"abc".to_string()`.
+fn parse_literal(expression: &str) -> Option<String> {
+    let expression = expression.trim();
+
+    if let Some(string) = expression.strip_prefix('"') {
+        return string.split('"').next().map(String::from);
+    }
+
+    let expression = expression.replace('_', "");
+    let end = expression
+        .find(|c: char| !c.is_ascii_digit() && c != '-')
+        .unwrap_or(expression.len());
+    let (digits, suffix) = expression.split_at(end);
+
+    // anything but a type suffix makes it an expression.
+    if !suffix.is_empty() && !suffix.starts_with(['u', 'i']) {
+        return None;
+    }
+
+    digits.parse::<i128>().ok().map(|value| value.to_string())
+}
+
+/// Runs the tests of the day, which check the examples.
+fn run_tests(bin_name: &str) -> Option<String> {
+    println!("Running the tests of {bin_name} before submitting...");
+
+    let output = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", bin_name])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            Some(format!("the tests of {bin_name} fail."))
+        }
+        Err(e) => Some(format!("the tests could not be run: {e}")),
+    }
+}
+
+/// Asks whether `answer` should be submitted, anything but yes declines.
+#[must_use]
+pub fn confirm(day: u8, part: u8, answer: &str) -> bool {
+    print!("Submit {answer} as the answer to part {part} of day {day}? [y/N] ");
+    let _ = stdout().flush();
+
+    let mut line = String::new();
+    if stdin().read_line(&mut line).is_err() {
+        return false;
+    }
+
+    matches!(line.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_empty, check_example, check_overflow, example_answers, parse_literal};
+
+    #[test]
+    fn rejects_empty_answers() {
+        assert!(check_empty("").is_some());
+        assert!(check_empty("0").is_some());
+        assert!(check_empty("10").is_none());
+    }
+
+    #[test]
+    fn rejects_overflowed_answers() {
+        assert!(check_overflow("4294967295").is_some());
+        assert!(check_overflow("4294967290").is_some());
+        assert!(check_overflow("-2147483648").is_some());
+        assert!(check_overflow("18446744073709551615").is_some());
+        assert!(check_overflow("4000000000").is_none());
+        assert!(check_overflow("82000210").is_none());
+        assert!(check_overflow("abc").is_none());
+    }
+
+    #[test]
+    fn parses_literals() {
+        assert_eq!(parse_literal("142"), Some("142".into()));
+        assert_eq!(parse_literal("82_000_210u64"), Some("82000210".into()));
+        assert_eq!(parse_literal("-5"), Some("-5".into()));
+        assert_eq!(parse_literal("\"abc\".to_string()"), Some("abc".into()));
+        assert_eq!(parse_literal("1 + 2"), None);
+        assert_eq!(parse_literal("x"), None);
+    }
+
+    #[test]
+    fn finds_example_answers() {
+        let source = r#"
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&read_file("examples", 1));
+        assert_eq!(result, Some(142));
+    }
+
+    #[test]
+    fn test_part_one_sum() {
+        let result = part_one("1abc2");
+        assert_eq!(result, Some(1 + 2));
+    }
+
+    #[test]
+    fn test_part_one_joker() {
+        let result = part_one("1abc2");
+        assert_eq!(result, Some(12));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&read_file("examples", 1));
+        assert_eq!(result, Some(281));
+        let result = part_two(&read_file("examples", 2));
+        assert_eq!(result, Some(77));
+    }
+"#;
+
+        assert_eq!(example_answers(source, 1), vec!["142".to_string()]);
+        assert_eq!(
+            example_answers(source, 2),
+            vec!["281".to_string(), "77".to_string()]
+        );
+        assert!(check_example("142", &example_answers(source, 1)).is_some());
+        assert!(check_example("281", &example_answers(source, 1)).is_none());
+    }
+}
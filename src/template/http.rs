@@ -0,0 +1,98 @@
+/// Minimal HTTP client for requests that aoc-cli does not cover, wrapping the "curl" command-line.
+/// Requests go to the `url` of the project config, which can point to a local mock of the site.
+use std::{
+    fmt::Display,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::template::config::Config;
+
+/// Identifies this repository to the site, as requested by its maintainers.
+pub const USER_AGENT: &str = "github.com/magnusrodseth/aoc-2023";
+
+#[derive(Debug)]
+pub enum Error {
+    CommandNotFound,
+    /// curl could not complete the request, e.g. because the site is unreachable.
+    Request(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::CommandNotFound => write!(f, "curl is not present in environment."),
+            Error::Request(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Fetches `path` of the site with the session cookie `token`, following redirects.
+pub fn get(path: &str, token: &str) -> Result<Response, Error> {
+    let url = format!("{}{path}", Config::get().url.trim_end_matches('/'));
+
+    // the cookie is passed on stdin to keep it out of the process list.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--location", "--max-time", "15"])
+        .args(["--user-agent", USER_AGENT, "--header", "@-"])
+        .args(["--write-out", "\n%{http_code}", &url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| Error::CommandNotFound)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: session={token}").map_err(|e| Error::Request(e.to_string()))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| Error::Request(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Request(stderr.trim().into()));
+    }
+
+    parse_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Splits curl's output into the body and the status code written after it.
+fn parse_output(output: &str) -> Result<Response, Error> {
+    let (body, status) = output
+        .rsplit_once('\n')
+        .ok_or_else(|| Error::Request("missing status code".into()))?;
+
+    let status = status
+        .trim()
+        .parse()
+        .map_err(|_| Error::Request(format!("invalid status code \"{status}\"")))?;
+
+    Ok(Response {
+        status,
+        body: body.into(),
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_output;
+
+    #[test]
+    fn parses_output() {
+        let response = parse_output("<html>\n</html>\n200").unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "<html>\n</html>");
+
+        assert_eq!(parse_output("\n404").unwrap().status, 404);
+        assert!(parse_output("200").is_err());
+        assert!(parse_output("body\nxyz").is_err());
+    }
+}
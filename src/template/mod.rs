@@ -1,34 +1,91 @@
-use std::{env, fs};
+use std::env;
 
 pub mod aoc_cli;
+pub mod checksums;
 pub mod commands;
+pub mod config;
+pub mod encryption;
+pub mod guards;
+pub mod http;
+pub mod params;
 pub mod readme_benchmarks;
 pub mod runner;
+pub mod scaling;
+pub mod session;
+pub mod submission;
+pub mod viz;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
 /// Helper function that reads a text file to a string.
+/// The directory of `folder` comes from the `[data]` section of the project config.
+/// Missing files are decrypted from their encrypted copy, see `template::encryption`.
 #[must_use] pub fn read_file(folder: &str, day: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
+        .join(config::Config::get().data_dir(folder))
         .join(format!("{day:02}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = encryption::read_to_string(&filepath);
     f.expect("could not open input file")
 }
 
 /// main! produces a block setting up the input and runner for each part.
+/// Options can be appended to the day, `params` first, e.g. `main!(11, params, viz, scale)`:
+///  - `params`: each part also receives the `--param` values as a `&Params`.
+///  - `viz`: `visualize` is called after both parts when `--viz` is passed, with the input, the params
+///    if any, and a `&mut Visualizer`.
+///  - `explain`: `explain` is called with the input after both parts when `--explain` is passed.
+///  - `scale`: with `--scale`, both parts are benched on inputs from `generate(n, rng)` of increasing
+///    size instead of the puzzle input, see `template::scaling`.
 #[macro_export]
 macro_rules! main {
-    ($day:expr) => {
+    (@run $day:expr, $part_one:ident, $part_two:ident, $extra:tt, $($option:ident)*) => {
+        $(advent_of_code::main!(@before $option, $part_one, $part_two);)*
+
+        let input = advent_of_code::template::read_file("inputs", $day);
+        run_part($part_one, input.as_str(), $day, 1);
+        run_part($part_two, input.as_str(), $day, 2);
+
+        $(advent_of_code::main!(@after $option, $day, input, $extra);)*
+    };
+    (@before scale, $part_one:ident, $part_two:ident) => {
+        if advent_of_code::template::scaling::requested() {
+            advent_of_code::template::scaling::run(generate, $part_one, $part_two);
+            return;
+        }
+    };
+    (@before viz, $part_one:ident, $part_two:ident) => {};
+    (@before explain, $part_one:ident, $part_two:ident) => {};
+    (@after viz, $day:expr, $input:ident, [$($extra:expr),*]) => {
+        if let Some(mut viz) = advent_of_code::template::viz::Visualizer::from_args($day) {
+            visualize(&$input, $($extra,)* &mut viz);
+            viz.finish();
+        }
+    };
+    (@after explain, $day:expr, $input:ident, $extra:tt) => {
+        if std::env::args().any(|x| x == "--explain") {
+            explain(&$input);
+        }
+    };
+    (@after scale, $day:expr, $input:ident, $extra:tt) => {};
+    ($day:expr, params $(, $option:ident)*) => {
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let params = advent_of_code::template::params::Params::from_args();
+            let part_one = |input: &str| part_one(input, &params);
+            let part_two = |input: &str| part_two(input, &params);
+            advent_of_code::main!(@run $day, part_one, part_two, [&params], $($option)*);
+        }
+    };
+    ($day:expr $(, $option:ident)*) => {
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", $day);
-            run_part(part_one, &input, $day, 1);
-            run_part(part_two, &input, $day, 2);
+            advent_of_code::main!(@run $day, part_one, part_two, [], $($option)*);
         }
     };
 }
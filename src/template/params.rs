@@ -0,0 +1,119 @@
+/// Runtime parameters for solutions, passed as `cargo solve <day> --param <key>=<value>`.
+/// Solutions opt in with `main!(<day>, params)` and receive the parsed parameters as a second argument.
+use std::{collections::HashMap, env, fmt::Display, process, str::FromStr};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    /// Parses all `--param <key>=<value>` pairs from the command-line arguments of the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        match Self::parse(&args) {
+            Ok(params) => params,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Parses all `--param <key>=<value>` pairs in `args`, ignoring other arguments.
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut params = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if arg != "--param" {
+                continue;
+            }
+
+            let (key, value) = args
+                .next()
+                .and_then(|pair| pair.split_once('='))
+                .ok_or("Unexpected command-line input. Format: --param <key>=<value>")?;
+
+            params.values.insert(key.trim().into(), value.trim().into());
+        }
+
+        Ok(params)
+    }
+
+    /// Sets a parameter, e.g. to run a test against an example that uses different values.
+    #[must_use]
+    pub fn with(mut self, key: &str, value: impl Display) -> Self {
+        self.values.insert(key.into(), value.to_string());
+        self
+    }
+
+    /// Looks up a parameter and parses it as `T`.
+    ///
+    /// # Panics
+    /// Panics if the parameter is set but cannot be parsed as `T`.
+    #[must_use]
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.values.get(key).map(|value| {
+            value.parse().unwrap_or_else(|_| {
+                panic!(
+                    "invalid value \"{value}\" for parameter \"{key}\", expected {}",
+                    std::any::type_name::<T>()
+                )
+            })
+        })
+    }
+
+    /// Like [`Params::get`], falling back to `default` if the parameter is not set.
+    #[must_use]
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> T {
+        self.get(key).unwrap_or(default)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Params;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_params() {
+        let params = Params::parse(&args(
+            "target/debug/11 --time --param expansion=100 --param label=AAA",
+        ))
+        .unwrap();
+        assert_eq!(params.get::<u64>("expansion"), Some(100));
+        assert_eq!(params.get::<String>("label"), Some("AAA".into()));
+        assert_eq!(params.get::<u64>("missing"), None);
+        assert_eq!(params.get_or("missing", 2), 2);
+    }
+
+    #[test]
+    fn rejects_malformed_params() {
+        assert!(Params::parse(&args("--param expansion")).is_err());
+        assert!(Params::parse(&args("--param")).is_err());
+    }
+
+    #[test]
+    fn sets_params() {
+        let params = Params::default().with("expansion", 10);
+        assert_eq!(params.get::<u64>("expansion"), Some(10));
+        assert_eq!(
+            params,
+            Params::parse(&args("--param expansion=10")).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_invalid_value() {
+        let _ = Params::default()
+            .with("expansion", "ten")
+            .get::<u64>("expansion");
+    }
+}
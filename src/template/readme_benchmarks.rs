@@ -1,8 +1,10 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+/// The README path and the marker come from the `[readme]` section of the project config. In the
+/// multi-year layout, each year has its own table.
 use std::{fs, io};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+use crate::template::config::{Config, Layout, YEAR_PLACEHOLDER};
 
 #[derive(Debug)]
 pub enum Error {
@@ -29,13 +31,8 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use] pub fn get_path_for_bin(day: usize) -> String {
-    let day_padded = format!("{day:02}");
-    format!("./src/bin/{day_padded}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -56,11 +53,17 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+fn construct_table(
+    prefix: &str,
+    config: &Config,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> String {
     let header = format!("{prefix} Benchmarks");
+    let marker = config.readme_marker();
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
         "| Day | Part 1 | Part 2 |".into(),
@@ -68,7 +71,8 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     ];
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
+        #[allow(clippy::cast_possible_truncation)]
+        let path = config.bin_path(timing.day as u8);
         lines.push(format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day,
@@ -80,29 +84,54 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    config: &Config,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let marker = config.readme_marker();
+
+    // years added to a multi-year repository get a new table after the other years' tables.
+    if config.layout == Layout::MultiYear && !s.contains(&marker) {
+        let other_years = config.readme.marker.split(YEAR_PLACEHOLDER).next().unwrap();
+        let pos = s.rfind(other_years).map_or(s.len(), |i| {
+            s[i..].find('\n').map_or(s.len(), |end| i + end)
+        });
+        s.insert_str(pos, &format!("\n\n{marker}{marker}"));
+    }
+
+    let prefix = match (config.layout, config.year) {
+        (Layout::MultiYear, Some(year)) => format!("## {year}"),
+        _ => "##".into(),
+    };
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table(&prefix, config, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
+/// Writes the benchmark table of the year selected in `config`.
+pub fn update(config: &Config, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+    let path = &config.readme.path;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, config, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{update_content, Timings};
+    use crate::template::config::Config;
+
+    const MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -131,28 +160,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &Config::default(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &Config::default(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &Config::default(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &Config::default(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &Config::default(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +189,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &Config::default(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +209,26 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn adds_tables_per_year() {
+        let multi_year = Config::parse("layout = \"multi-year\"").unwrap();
+        let marker_2023 = "<!--- benchmarking table 2023 --->";
+        let mut s = format!("foo\n{marker_2023}{marker_2023}\n\nbaz");
+
+        for year in [2022, 2023] {
+            let config = multi_year.for_year(year);
+            update_content(&mut s, &config, get_mock_timings(), 190.0).unwrap();
+        }
+
+        let lines = s.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[1..3], [marker_2023, "## 2023 Benchmarks"]);
+        assert!(lines.contains(&"| [Day 4](./src/bin/2023-04.rs) | `40ms` | `50ms` |"));
+        assert_eq!(
+            lines[11..14],
+            [marker_2023, "", "<!--- benchmarking table 2022 --->"]
+        );
+        assert!(lines.contains(&"## 2022 Benchmarks"));
+        assert!(s.ends_with("<!--- benchmarking table 2022 --->\n\nbaz"));
+    }
 }
@@ -1,10 +1,11 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_cli, config::Config, guards, session, submission::Verdict, ANSI_ITALIC, ANSI_RESET,
+};
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
 use super::ANSI_BOLD;
 
@@ -17,7 +18,17 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        match submit_result(result, day, part) {
+            Some(Ok(verdict)) => {
+                verdict.print();
+
+                if part == 1 && verdict.is_correct() {
+                    refresh_description(day);
+                }
+            }
+            Some(Err(e)) => eprintln!("failed to call aoc-cli: {e}"),
+            None => {}
+        }
     }
 }
 
@@ -36,6 +47,8 @@ fn run_timed<I: Clone, T>(
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -44,17 +57,20 @@ fn run_timed<I: Clone, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
+/// Runs `func` repeatedly, for about a second but at least 10 and at most 10000 times, and returns the
+/// average duration and the number of samples. `base_time` is the duration of a first run.
+/// The limits can be changed in the `[bench]` section of the project config.
+pub fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128) {
+    let settings = &Config::get().bench;
     let bench_iterations = cmp::min(
-        10000,
+        settings.max_samples,
         cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
+            settings.duration.as_nanos() / cmp::max(base_time.as_nanos(), 10),
+            settings.min_samples,
         ),
     );
 
@@ -129,11 +145,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the checks of `guards` pass, unless `--force` is passed.
+///  4. the submission is confirmed, unless `--yes` is passed.
 fn submit_result<T: Display>(
     result: T,
     day: u8,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Verdict, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -156,11 +174,78 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let answer = result.to_string();
+
+    if !args.contains(&"--force".into()) {
+        let problems = guards::check(day, part, &answer);
+
+        if !problems.is_empty() {
+            eprintln!("Not submitting part {part}:");
+            for problem in problems {
+                eprintln!("  - {problem}");
+            }
+            eprintln!("Pass --force to submit it anyway.");
+            return None;
+        }
+    }
+
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
+    session::preflight();
+
+    if !args.contains(&"--yes".into()) && !guards::confirm(day, part, &answer) {
+        println!("Not submitted.");
+        return None;
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &answer))
+}
+
+/// The description only contains part 2 once part 1 is solved, so it is fetched again after that.
+/// The input is left as it is.
+fn refresh_description(day: u8) {
+    println!("Fetching the description of part 2...");
+
+    if let Err(e) = aoc_cli::download_puzzle(day) {
+        eprintln!("failed to refresh the puzzle description: {e}");
+        return;
+    }
+
+    let puzzle_path = aoc_cli::get_puzzle_path(day);
+    let description = fs::read_to_string(&puzzle_path).unwrap_or_default();
+
+    match part_two(&description) {
+        Some(section) => {
+            println!("---");
+            println!("{section}");
+        }
+        None => eprintln!("Warning: \"{puzzle_path}\" does not contain part 2."),
+    }
+}
+
+/// The part 2 section of a description, from its heading to the end.
+fn part_two(description: &str) -> Option<&str> {
+    let heading = description.find("--- Part Two ---")?;
+    let line_start = description[..heading].rfind('\n').map_or(0, |i| i + 1);
+    Some(description[line_start..].trim_end())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::part_two;
+
+    #[test]
+    fn finds_part_two() {
+        let description =
+            "## --- Day 1: Trebuchet?! ---\n\nPart 1.\n\n## --- Part Two ---\n\nPart 2.\n";
+        assert_eq!(
+            part_two(description),
+            Some("## --- Part Two ---\n\nPart 2.")
+        );
+        assert_eq!(part_two("## --- Day 1: Trebuchet?! ---\n\nPart 1.\n"), None);
+    }
 }
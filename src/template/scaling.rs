@@ -0,0 +1,222 @@
+/// Empirical complexity estimation, run with `cargo scale <day>`.
+/// Solutions opt in with `main!(<day>, scale)` and a `generate(n, rng)` function that returns a synthetic
+/// input of size `n` in the puzzle's format. Both parts are benched on inputs of doubling size, and the
+/// timings are fitted to common complexity classes.
+use std::{
+    env,
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+use crate::differential::Rng;
+use crate::template::{runner::bench, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Smallest input size.
+const MIN_SIZE: usize = 64;
+/// Largest input size.
+const MAX_SIZE: usize = 1 << 20;
+/// Sizes stop doubling once a single run takes longer than this.
+const MAX_RUN_TIME: Duration = Duration::from_millis(100);
+/// Number of the largest sizes used for fitting. Small inputs mostly measure constant overhead.
+const FITTED_POINTS: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Complexity {
+    const ALL: [Complexity; 6] = [
+        Complexity::Constant,
+        Complexity::Logarithmic,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+    ];
+
+    /// Natural logarithm of the growth function at `n`.
+    fn ln_growth(self, n: f64) -> f64 {
+        match self {
+            Complexity::Constant => 0.0,
+            Complexity::Logarithmic => n.ln().ln(),
+            Complexity::Linear => n.ln(),
+            Complexity::Linearithmic => n.ln() + n.ln().ln(),
+            Complexity::Quadratic => 2.0 * n.ln(),
+            Complexity::Cubic => 3.0 * n.ln(),
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Logarithmic => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+        };
+
+        f.pad(name)
+    }
+}
+
+/// Fits `time = c * f(n)` for each complexity class by least squares on a log scale, and returns the
+/// class with the smallest residual. Needs at least three samples of `(n, seconds)`.
+#[must_use]
+pub fn estimate(samples: &[(usize, f64)]) -> Option<Complexity> {
+    if samples.len() < 3 {
+        return None;
+    }
+
+    let residual = |complexity: Complexity| {
+        let offsets = samples
+            .iter()
+            .map(|&(n, time)| time.ln() - complexity.ln_growth(n as f64))
+            .collect::<Vec<f64>>();
+        let ln_c = offsets.iter().sum::<f64>() / offsets.len() as f64;
+        offsets.iter().map(|o| (o - ln_c).powi(2)).sum::<f64>()
+    };
+
+    Complexity::ALL
+        .into_iter()
+        .map(|complexity| (complexity, residual(complexity)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(complexity, _)| complexity)
+}
+
+/// Slope of `ln(time)` over `ln(n)`, i.e. the exponent `k` of the best fitting `n^k`.
+#[must_use]
+pub fn log_log_slope(samples: &[(usize, f64)]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let points = samples
+        .iter()
+        .map(|&(n, time)| ((n as f64).ln(), time.ln()))
+        .collect::<Vec<(f64, f64)>>();
+    let len = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / len;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / len;
+
+    let covariance = points
+        .iter()
+        .map(|p| (p.0 - mean_x) * (p.1 - mean_y))
+        .sum::<f64>();
+    let variance = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum::<f64>();
+
+    Some(covariance / variance)
+}
+
+/// Whether the current process was started with `--scale`.
+#[must_use]
+pub fn requested() -> bool {
+    env::args().any(|x| x == "--scale")
+}
+
+/// Benches a part on generated inputs of doubling size and prints the timings and the estimate.
+fn scale_part<T>(
+    part: u8,
+    generate: &impl Fn(usize, &mut Rng) -> String,
+    func: impl Fn(&str) -> Option<T>,
+) {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+    println!("{:>10}  {:>12}", "n", "time");
+
+    let mut rng = Rng::new(crate::differential::SEED);
+    let mut samples = vec![];
+    let mut size = MIN_SIZE;
+
+    while size <= MAX_SIZE {
+        let input = generate(size, &mut rng);
+
+        let timer = Instant::now();
+        func(&input);
+        let base_time = timer.elapsed();
+
+        let (duration, _) = bench(&func, input.as_str(), &base_time);
+        println!("{size:>10}  {:>12}", format!("{duration:.1?}"));
+        samples.push((size, duration.as_secs_f64().max(1e-9)));
+
+        if base_time > MAX_RUN_TIME {
+            break;
+        }
+        size *= 2;
+    }
+
+    let fitted = &samples[samples.len().saturating_sub(FITTED_POINTS)..];
+
+    match (estimate(fitted), log_log_slope(fitted)) {
+        (Some(complexity), Some(slope)) => println!(
+            "Estimate: {ANSI_BOLD}{complexity}{ANSI_RESET} {ANSI_ITALIC}(time grows like n^{slope:.2}){ANSI_RESET}"
+        ),
+        _ => println!("Estimate: not enough sizes ran within {MAX_RUN_TIME:?}."),
+    }
+}
+
+/// Runs [`scale_part`] for both parts, see the module documentation.
+pub fn run<T1, T2>(
+    generate: impl Fn(usize, &mut Rng) -> String,
+    part_one: impl Fn(&str) -> Option<T1>,
+    part_two: impl Fn(&str) -> Option<T2>,
+) {
+    scale_part(1, &generate, part_one);
+    println!();
+    scale_part(2, &generate, part_two);
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{estimate, log_log_slope, Complexity};
+
+    fn samples(f: impl Fn(f64) -> f64) -> Vec<(usize, f64)> {
+        (10..16)
+            .map(|k| 1_usize << k)
+            .map(|n| (n, 1e-9 * f(n as f64)))
+            .collect()
+    }
+
+    #[test]
+    fn estimates_complexity() {
+        assert_eq!(estimate(&samples(|_| 50.0)), Some(Complexity::Constant));
+        assert_eq!(
+            estimate(&samples(|n| 7.0 * n.ln())),
+            Some(Complexity::Logarithmic)
+        );
+        assert_eq!(estimate(&samples(|n| 3.0 * n)), Some(Complexity::Linear));
+        assert_eq!(
+            estimate(&samples(|n| n * n.ln())),
+            Some(Complexity::Linearithmic)
+        );
+        assert_eq!(
+            estimate(&samples(|n| n * n / 10.0)),
+            Some(Complexity::Quadratic)
+        );
+        assert_eq!(estimate(&samples(|n| n * n * n)), Some(Complexity::Cubic));
+        assert_eq!(estimate(&samples(|n| n)[..2]), None);
+    }
+
+    #[test]
+    fn tolerates_noise() {
+        // Linear with a constant overhead and ±10% jitter.
+        let noisy = samples(|n| {
+            let jitter = if n.log2() as usize % 2 == 0 { 1.1 } else { 0.9 };
+            (200.0 + 5.0 * n) * jitter
+        });
+        assert_eq!(estimate(&noisy), Some(Complexity::Linear));
+    }
+
+    #[test]
+    fn computes_slope() {
+        let slope = log_log_slope(&samples(|n| n * n)).unwrap();
+        assert!((slope - 2.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,301 @@
+/// Management of the session cookie that authenticates downloads and submissions.
+/// The token is stored in the session file of the project config, `~/.adventofcode.session` by
+/// default, which aoc-cli reads as well. Tokens are checked against the site before they are used.
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{config::Config, http, ANSI_BOLD, ANSI_RESET};
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+/// Sessions of the site last about a month.
+const LIFETIME: Duration = Duration::from_secs(30 * DAY_SECS);
+/// How long before the expected expiry to start warning.
+const WARN_BEFORE: Duration = Duration::from_secs(5 * DAY_SECS);
+
+#[derive(Debug)]
+pub enum Error {
+    Missing(PathBuf),
+    Malformed(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Missing(path) => write!(
+                f,
+                "no session token found in \"{}\". Run `cargo session` to store one.",
+                path.display()
+            ),
+            Error::Malformed(e) => write!(f, "malformed session token: {e}"),
+            Error::IO(e) => write!(f, "could not access the session file: {e}"),
+        }
+    }
+}
+
+/// A stored token and when it was saved.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub token: String,
+    pub path: PathBuf,
+    pub saved: SystemTime,
+}
+
+/// The result of checking a token against the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Valid {
+        user: String,
+    },
+    Unauthorized,
+    /// The site could not be asked, e.g. without a network connection.
+    Unknown(String),
+}
+
+/// The session file of the project config, or aoc-cli's default in the home directory.
+#[must_use]
+pub fn path() -> PathBuf {
+    Config::get().session_path().unwrap_or_else(|| {
+        let home = env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .unwrap_or_default();
+        Path::new(&home).join(".adventofcode.session")
+    })
+}
+
+/// Accepts the cookie value as copied from the browser, with or without the `session=` prefix.
+pub fn normalize(token: &str) -> Result<String, Error> {
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+
+    if token.is_empty() {
+        return Err(Error::Malformed("the token is empty".into()));
+    }
+
+    if !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::Malformed(
+            "expected hexadecimal characters, copy the value of the `session` cookie".into(),
+        ));
+    }
+
+    Ok(token.into())
+}
+
+/// Reads the stored token.
+pub fn load() -> Result<Session, Error> {
+    let path = path();
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(Error::Missing(path)),
+        Err(e) => return Err(e.into()),
+    };
+
+    let saved = fs::metadata(&path)?.modified()?;
+
+    Ok(Session {
+        token: normalize(&contents)?,
+        path,
+        saved,
+    })
+}
+
+/// Writes `token` to the session file, readable only by the current user.
+pub fn store(token: &str) -> Result<PathBuf, Error> {
+    let path = path();
+    write_private(&path, token)?;
+    Ok(path)
+}
+
+pub(crate) fn write_private(path: &Path, token: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+
+        // files created before are not affected by `mode`.
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    let mut file = options.open(path)?;
+    writeln!(file, "{token}")
+}
+
+/// Asks the site which account `token` belongs to.
+#[must_use]
+pub fn check(token: &str) -> Status {
+    let page = Config::get()
+        .year
+        .map_or_else(|| "/".to_string(), |year| format!("/{year}"));
+
+    match http::get(&page, token) {
+        Ok(response) if response.status == 200 => {
+            parse_user(&response.body).map_or(Status::Unauthorized, |user| Status::Valid { user })
+        }
+        // the site answers invalid cookies with an error instead of a login page at times.
+        Ok(response) if matches!(response.status, 400 | 401 | 403 | 500) => Status::Unauthorized,
+        Ok(response) => Status::Unknown(format!("unexpected response status {}", response.status)),
+        Err(e) => Status::Unknown(e.to_string()),
+    }
+}
+
+/// The account name in the page header, which only logged in users see.
+fn parse_user(html: &str) -> Option<String> {
+    let start = html.find("<div class=\"user\">")? + "<div class=\"user\">".len();
+    let name = html[start..].split('<').next()?.trim();
+    (!name.is_empty()).then(|| name.into())
+}
+
+/// A warning if a session saved `age` ago expires soon or has probably expired.
+fn expiry_warning(age: Duration) -> Option<String> {
+    let days = age.as_secs() / DAY_SECS;
+
+    if age >= LIFETIME {
+        Some(format!(
+            "the session token was saved {days} days ago and has probably expired."
+        ))
+    } else if age + WARN_BEFORE >= LIFETIME {
+        Some(format!(
+            "the session token was saved {days} days ago and expires in about {} days.",
+            (LIFETIME - age).as_secs().div_ceil(DAY_SECS)
+        ))
+    } else {
+        None
+    }
+}
+
+/// Checks the stored token before a download or submission, so failures are explained up front.
+/// Exits if there is no token or the site rejects it, and warns if it expires soon.
+pub fn preflight() {
+    let session = match load() {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    match check(&session.token) {
+        Status::Valid { .. } => {
+            if let Some(warning) = SystemTime::now()
+                .duration_since(session.saved)
+                .ok()
+                .and_then(expiry_warning)
+            {
+                eprintln!("Warning: {warning} Refresh it with `cargo session`.");
+            }
+        }
+        Status::Unauthorized => {
+            eprintln!(
+                "Error: the site rejected the session token in \"{}\", it has probably expired. Refresh it with `cargo session`.",
+                session.path.display()
+            );
+            process::exit(1);
+        }
+        Status::Unknown(e) => eprintln!("Warning: could not check the session token, {e}"),
+    }
+}
+
+/// Prints where the token is stored, how old it is and which account it belongs to.
+pub fn print_status(session: &Session) {
+    let age = SystemTime::now()
+        .duration_since(session.saved)
+        .unwrap_or_default();
+
+    println!("Session file: \"{}\"", session.path.display());
+    println!("Saved: {} days ago", age.as_secs() / DAY_SECS);
+
+    match check(&session.token) {
+        Status::Valid { user } => println!("Account: {ANSI_BOLD}{user}{ANSI_RESET}"),
+        Status::Unauthorized => {
+            println!("Account: -");
+            eprintln!("Warning: the site rejected the token, it has probably expired.");
+        }
+        Status::Unknown(e) => eprintln!("Warning: could not check the token, {e}"),
+    }
+
+    if let Some(warning) = expiry_warning(age) {
+        eprintln!("Warning: {warning}");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{expiry_warning, normalize, parse_user, write_private, LIFETIME};
+    use std::{fs, time::Duration};
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[test]
+    fn normalizes_tokens() {
+        assert_eq!(normalize(" 53616c7465\n").unwrap(), "53616c7465");
+        assert_eq!(normalize("session=53616c7465").unwrap(), "53616c7465");
+        assert!(normalize("").is_err());
+        assert!(normalize("session=").is_err());
+        assert!(normalize("not a token").is_err());
+    }
+
+    #[test]
+    fn parses_user() {
+        let logged_in =
+            r#"<header><div class="user">Ferris <span class="star-count">42*</span></div>"#;
+        let logged_out = r#"<header><div><a href="/2023/auth/login">[Log In]</a></div>"#;
+
+        assert_eq!(parse_user(logged_in), Some("Ferris".into()));
+        assert_eq!(parse_user(logged_out), None);
+        assert_eq!(parse_user(r#"<div class="user"> </div>"#), None);
+    }
+
+    #[test]
+    fn warns_about_expiry() {
+        assert_eq!(expiry_warning(DAY * 3), None);
+        assert_eq!(
+            expiry_warning(DAY * 27),
+            Some("the session token was saved 27 days ago and expires in about 3 days.".into())
+        );
+        assert_eq!(
+            expiry_warning(LIFETIME + DAY),
+            Some("the session token was saved 31 days ago and has probably expired.".into())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stores_privately() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(&path, "53616c7465").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(contents, "53616c7465\n");
+    }
+}
@@ -0,0 +1,277 @@
+/// Classification of the site's responses to submitted answers.
+use std::{fmt::Display, time::Duration};
+
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW};
+
+/// How `cargo solve --submit <part>` submits an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubmitOptions {
+    pub part: u8,
+    /// Skip the confirmation prompt.
+    pub yes: bool,
+    /// Submit even if a check of [`crate::template::guards`] fails.
+    pub force: bool,
+}
+
+impl SubmitOptions {
+    /// Reads `--submit <part>`, `--yes` and `--force`. Returns `None` without `--submit`.
+    pub fn from_arguments(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Self>, pico_args::Error> {
+        let part: Option<u8> = args.opt_value_from_str("--submit")?;
+        let yes = args.contains("--yes");
+        let force = args.contains("--force");
+
+        Ok(part.map(|part| Self { part, yes, force }))
+    }
+
+    /// The arguments that produce these options, for passing them on to a solution.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--submit".to_string(), self.part.to_string()];
+        if self.yes {
+            args.push("--yes".into());
+        }
+        if self.force {
+            args.push("--force".into());
+        }
+        args
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong answers are followed by a wait before the next attempt.
+    Incorrect {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    /// The previous answer was submitted too recently.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    AlreadySolved,
+    /// Part 2 was submitted before part 1 was solved.
+    WrongLevel,
+    /// A response that none of the above matches, as the site sent it.
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Classifies aoc-cli's output for a submission of `part`.
+    /// The site answers both answers to solved parts and to locked parts with the same message,
+    /// so `part_two_unlocked` tells them apart for part 2.
+    #[must_use]
+    pub fn parse(response: &str, part: u8, part_two_unlocked: bool) -> Self {
+        // aoc-cli wraps the text to the width of the terminal.
+        let text = response.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = if text.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if text.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+
+            Verdict::Incorrect {
+                hint,
+                wait: parse_wait(&text),
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited {
+                wait: parse_wait(&text),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            if part == 1 || part_two_unlocked {
+                Verdict::AlreadySolved
+            } else {
+                Verdict::WrongLevel
+            }
+        } else {
+            Verdict::Unknown(response.trim().into())
+        }
+    }
+
+    #[must_use]
+    pub fn is_correct(&self) -> bool {
+        *self == Verdict::Correct
+    }
+
+    /// Prints the verdict, coloured by whether the answer was accepted.
+    pub fn print(&self) {
+        let color = match self {
+            Verdict::Correct => ANSI_GREEN,
+            Verdict::Incorrect { .. } => ANSI_RED,
+            _ => ANSI_YELLOW,
+        };
+
+        println!("{color}{ANSI_BOLD}{self}{ANSI_RESET}");
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "✔ Correct answer!"),
+            Verdict::Incorrect { hint, wait } => {
+                write!(f, "✖ Incorrect answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", it is too high")?,
+                    Some(Hint::TooLow) => write!(f, ", it is too low")?,
+                    None => {}
+                }
+                match wait {
+                    Some(wait) => write!(f, ". Wait {} before trying again.", format_wait(*wait)),
+                    None => write!(f, "."),
+                }
+            }
+            Verdict::RateLimited { wait } => match wait {
+                Some(wait) => write!(
+                    f,
+                    "⏳ Answered too recently, wait {} before trying again.",
+                    format_wait(*wait)
+                ),
+                None => write!(f, "⏳ Answered too recently, wait before trying again."),
+            },
+            Verdict::AlreadySolved => write!(f, "★ This part is already solved."),
+            Verdict::WrongLevel => write!(f, "✖ Solve part 1 before submitting part 2."),
+            Verdict::Unknown(response) => write!(f, "? Unrecognized response:\n{response}"),
+        }
+    }
+}
+
+/// The wait in texts like "You have 1m 5s left to wait" or "please wait 5 minutes".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let text = text.to_lowercase();
+
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].rfind("you have ")? + "you have ".len();
+
+        return text[start..end]
+            .split_whitespace()
+            .map(|part| {
+                let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+                let value = value.parse::<u64>().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let start = text.find("please wait ")? + "please wait ".len();
+    let mut words = text[start..].split_whitespace();
+    let value = match words.next()? {
+        "one" => 1,
+        value => value.parse().ok()?,
+    };
+
+    match words.next()?.trim_end_matches('s') {
+        "minute" => Some(Duration::from_secs(value * 60)),
+        "second" => Some(Duration::from_secs(value)),
+        _ => None,
+    }
+}
+
+fn format_wait(wait: Duration) -> String {
+    match (wait.as_secs() / 60, wait.as_secs() % 60) {
+        (0, seconds) => format!("{seconds}s"),
+        (minutes, 0) => format!("{minutes}m"),
+        (minutes, seconds) => format!("{minutes}m {seconds}s"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Hint, Verdict};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_correct() {
+        let response = "That's the right answer! You are one gold star closer to\nsaving your vacation. [Continue to Part Two]";
+        assert_eq!(Verdict::parse(response, 1, false), Verdict::Correct);
+    }
+
+    #[test]
+    fn parses_incorrect() {
+        let response = "That's not the right answer; your answer is too\nhigh. If you're stuck, make sure you're using the full input data; there are also some general tips on the about page, or you can ask for hints on the subreddit. Please wait one minute before trying again. [Return to Day 1]";
+        assert_eq!(
+            Verdict::parse(response, 1, false),
+            Verdict::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+
+        let response = "That's not the right answer. If you're stuck, make sure you're using the full input data. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.";
+        assert_eq!(
+            Verdict::parse(response, 2, true),
+            Verdict::Incorrect {
+                hint: None,
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_rate_limit() {
+        let response = "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 37s left to wait. [Return to Day 1]";
+        assert_eq!(
+            Verdict::parse(response, 1, false),
+            Verdict::RateLimited {
+                wait: Some(Duration::from_secs(277))
+            }
+        );
+
+        let response = "You gave an answer too recently. You have 34s left to wait.";
+        assert_eq!(
+            Verdict::parse(response, 1, false),
+            Verdict::RateLimited {
+                wait: Some(Duration::from_secs(34))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_level() {
+        let response = "You don't seem to be solving the right level. Did you already complete it? [Return to Day 1]";
+        assert_eq!(Verdict::parse(response, 1, false), Verdict::AlreadySolved);
+        assert_eq!(Verdict::parse(response, 2, true), Verdict::AlreadySolved);
+        assert_eq!(Verdict::parse(response, 2, false), Verdict::WrongLevel);
+    }
+
+    #[test]
+    fn keeps_unknown_responses() {
+        assert_eq!(
+            Verdict::parse(" Something new. \n", 1, false),
+            Verdict::Unknown("Something new.".into())
+        );
+    }
+
+    #[test]
+    fn displays_verdicts() {
+        let verdict = Verdict::Incorrect {
+            hint: Some(Hint::TooLow),
+            wait: Some(Duration::from_secs(65)),
+        };
+        assert_eq!(
+            verdict.to_string(),
+            "✖ Incorrect answer, it is too low. Wait 1m 5s before trying again."
+        );
+    }
+}
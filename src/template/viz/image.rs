@@ -0,0 +1,447 @@
+/// Exports frames as images: a PNG of the final frame and an animated GIF of all frames.
+/// There are no fonts, so every cell is drawn as a small glyph: box-drawing characters as pipes,
+/// `.` as a dot, `█` as a filled cell and any other symbol as a filled square. Captions are not drawn.
+use std::{
+    fmt::{self, Display},
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use super::{Color, Frame};
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    Png(png::EncodingError),
+    Gif(gif::EncodingError),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<png::EncodingError> for Error {
+    fn from(e: png::EncodingError) -> Self {
+        Error::Png(e)
+    }
+}
+
+impl From<gif::EncodingError> for Error {
+    fn from(e: gif::EncodingError) -> Self {
+        Error::Gif(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Png(e) => write!(f, "could not encode PNG: {e}"),
+            Error::Gif(e) => write!(f, "could not encode GIF: {e}"),
+        }
+    }
+}
+
+type Rgb = [u8; 3];
+
+/// Names of the palette entries, in the order of their indices in exported images.
+const ENTRIES: [&str; 11] = [
+    "background",
+    "foreground",
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "gray",
+];
+
+/// The colours used for exported images: the background, the foreground of unstyled cells, and one
+/// colour per [`Color`].
+///
+/// Palettes are configured as a base palette, `dark` or `light`, followed by overrides,
+/// e.g. `light,yellow=d79921,background=ffffff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    entries: [Rgb; 11],
+}
+
+impl Palette {
+    pub const DARK: Palette = Palette {
+        entries: [
+            [0x1d, 0x20, 0x21],
+            [0xeb, 0xdb, 0xb2],
+            [0x28, 0x28, 0x28],
+            [0xfb, 0x49, 0x34],
+            [0xb8, 0xbb, 0x26],
+            [0xfa, 0xbd, 0x2f],
+            [0x83, 0xa5, 0x98],
+            [0xd3, 0x86, 0x9b],
+            [0x8e, 0xc0, 0x7c],
+            [0xfb, 0xf1, 0xc7],
+            [0x66, 0x5c, 0x54],
+        ],
+    };
+
+    pub const LIGHT: Palette = Palette {
+        entries: [
+            [0xfb, 0xf1, 0xc7],
+            [0x3c, 0x38, 0x36],
+            [0x28, 0x28, 0x28],
+            [0xcc, 0x24, 0x1d],
+            [0x98, 0x97, 0x1a],
+            [0xd7, 0x99, 0x21],
+            [0x45, 0x85, 0x88],
+            [0xb1, 0x62, 0x86],
+            [0x68, 0x9d, 0x6a],
+            [0xff, 0xff, 0xff],
+            [0xbd, 0xae, 0x93],
+        ],
+    };
+
+    const BACKGROUND: u8 = 0;
+    const FOREGROUND: u8 = 1;
+
+    fn index(color: Option<Color>, default: u8) -> u8 {
+        match color {
+            None => default,
+            Some(color) => 2 + color as u8,
+        }
+    }
+
+    /// Sets the colour of an entry, e.g. `"yellow"` or `"background"`.
+    #[must_use]
+    pub fn with(mut self, entry: &str, rgb: Rgb) -> Option<Self> {
+        let index = ENTRIES.iter().position(|&e| e == entry)?;
+        self.entries[index] = rgb;
+        Some(self)
+    }
+
+    /// The entries as consecutive RGB triples, the format PNG and GIF palettes use.
+    fn flattened(&self) -> Vec<u8> {
+        self.entries.concat()
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').map(str::trim);
+
+        let mut palette = match parts.next() {
+            Some("dark") => Self::DARK,
+            Some("light") => Self::LIGHT,
+            _ => {
+                return Err(format!(
+                    "palette \"{s}\" must start with \"dark\" or \"light\""
+                ))
+            }
+        };
+
+        for part in parts {
+            let (entry, hex) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected <entry>=<rrggbb>, got \"{part}\""))?;
+
+            let hex = hex.trim_start_matches('#');
+            let rgb = u32::from_str_radix(hex, 16)
+                .ok()
+                .filter(|_| hex.len() == 6)
+                .ok_or_else(|| format!("invalid colour \"{hex}\", expected rrggbb"))?
+                .to_be_bytes();
+
+            palette = palette
+                .with(entry, [rgb[1], rgb[2], rgb[3]])
+                .ok_or_else(|| format!("unknown palette entry \"{entry}\""))?;
+        }
+
+        Ok(palette)
+    }
+}
+
+/// Writes the palette as whichever of `dark` or `light` it is closer to, plus the entries that differ
+/// from it, so it parses back.
+impl Display for Palette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let differences = |base: &Palette| {
+            (0..ENTRIES.len())
+                .filter(|&i| self.entries[i] != base.entries[i])
+                .count()
+        };
+
+        let (name, base) = if differences(&Self::LIGHT) < differences(&Self::DARK) {
+            ("light", Self::LIGHT)
+        } else {
+            ("dark", Self::DARK)
+        };
+
+        write!(f, "{name}")?;
+
+        for ((entry, rgb), base) in ENTRIES.iter().zip(self.entries).zip(base.entries) {
+            if rgb != base {
+                write!(f, ",{entry}={:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// How frames are drawn as images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageOptions {
+    /// Width and height of a cell, in pixels.
+    pub cell_size: u32,
+    pub palette: Palette,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 8,
+            palette: Palette::default(),
+        }
+    }
+}
+
+/// An image with one palette index per pixel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Palette::BACKGROUND; width * height],
+        }
+    }
+
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, index: u8) {
+        for row in y..(y + height).min(self.height) {
+            let start = row * self.width;
+            self.pixels[start + x.min(self.width)..start + (x + width).min(self.width)].fill(index);
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Palette index of the pixel at `(x, y)`.
+    #[must_use]
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+}
+
+/// Which edges of a cell a pipe connects to, as `[up, down, left, right]`.
+fn pipe_edges(symbol: char) -> Option<[bool; 4]> {
+    match symbol {
+        '│' => Some([true, true, false, false]),
+        '─' => Some([false, false, true, true]),
+        '└' => Some([true, false, false, true]),
+        '┘' => Some([true, false, true, false]),
+        '┐' => Some([false, true, true, false]),
+        '┌' => Some([false, true, false, true]),
+        _ => None,
+    }
+}
+
+/// Draws `frame` with `cell_size` pixels per cell, on a canvas of at least `rows` by `cols` cells.
+/// Frames of an animation can differ in size, so they are drawn on a canvas that fits the largest.
+#[must_use]
+pub fn render(frame: &Frame, cell_size: u32, rows: usize, cols: usize) -> Canvas {
+    let size = cell_size.max(1) as usize;
+    let mut canvas = Canvas::new(cols.max(frame.cols()) * size, rows.max(frame.rows()) * size);
+
+    let thickness = (size / 4).max(1);
+    // Offset of a centred line of `thickness` pixels.
+    let centre = (size - thickness) / 2;
+    let margin = size / 6;
+
+    for row in 0..frame.rows() {
+        for col in 0..frame.cols() {
+            let cell = frame.get((row, col)).unwrap();
+            let (x, y) = (col * size, row * size);
+            let background = Palette::index(cell.style.background, Palette::BACKGROUND);
+            let foreground = Palette::index(cell.style.foreground, Palette::FOREGROUND);
+
+            canvas.fill(x, y, size, size, background);
+
+            if let Some([up, down, left, right]) = pipe_edges(cell.symbol) {
+                if up {
+                    canvas.fill(x + centre, y, thickness, centre + thickness, foreground);
+                }
+                if down {
+                    canvas.fill(x + centre, y + centre, thickness, size - centre, foreground);
+                }
+                if left {
+                    canvas.fill(x, y + centre, centre + thickness, thickness, foreground);
+                }
+                if right {
+                    canvas.fill(x + centre, y + centre, size - centre, thickness, foreground);
+                }
+                continue;
+            }
+
+            match cell.symbol {
+                ' ' => {}
+                '█' => canvas.fill(x, y, size, size, foreground),
+                '.' => canvas.fill(x + centre, y + centre, thickness, thickness, foreground),
+                _ => canvas.fill(
+                    x + margin,
+                    y + margin,
+                    size - 2 * margin,
+                    size - 2 * margin,
+                    foreground,
+                ),
+            }
+        }
+    }
+
+    canvas
+}
+
+/// Writes the last frame to `<path>.png` and all frames to `<path>.gif`, showing each for `1 / fps`
+/// seconds and the last one for three seconds. The GIF is skipped if it would be wider or higher than
+/// 65535 pixels. Returns the paths of the written files.
+pub fn export(
+    frames: &[Frame],
+    path: &Path,
+    options: &ImageOptions,
+    fps: u32,
+) -> Result<Vec<PathBuf>, Error> {
+    let Some(last) = frames.last() else {
+        return Ok(vec![]);
+    };
+
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+
+    let rows = frames.iter().map(Frame::rows).max().unwrap_or(0);
+    let cols = frames.iter().map(Frame::cols).max().unwrap_or(0);
+    let palette = options.palette.flattened();
+
+    let png_path = path.with_extension("png");
+    let canvas = render(last, options.cell_size, last.rows(), last.cols());
+    #[allow(clippy::cast_possible_truncation)]
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(File::create(&png_path)?),
+        canvas.width as u32,
+        canvas.height as u32,
+    );
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette.clone());
+    encoder.write_header()?.write_image_data(&canvas.pixels)?;
+
+    let gif_path = path.with_extension("gif");
+    let size = render(last, options.cell_size, rows, cols);
+    // GIF dimensions are 16 bit, larger animations only get the PNG.
+    let (Ok(width), Ok(height)) = (u16::try_from(size.width), u16::try_from(size.height)) else {
+        eprintln!(
+            "Warning: skipped the GIF, {}x{} pixels exceed its limit of {}x{}. Use a smaller --viz-cell-size.",
+            size.width,
+            size.height,
+            u16::MAX,
+            u16::MAX
+        );
+        return Ok(vec![png_path]);
+    };
+    let mut encoder = gif::Encoder::new(
+        BufWriter::new(File::create(&gif_path)?),
+        width,
+        height,
+        &palette,
+    )?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for (i, frame) in frames.iter().enumerate() {
+        let canvas = render(frame, options.cell_size, rows, cols);
+        let mut gif_frame = gif::Frame::from_indexed_pixels(width, height, canvas.pixels, None);
+        // GIF delays are in hundredths of a second.
+        gif_frame.delay = if i + 1 == frames.len() {
+            300
+        } else {
+            u16::try_from(100 / fps.max(1)).unwrap_or(1).max(1)
+        };
+        encoder.write_frame(&gif_frame)?;
+    }
+
+    Ok(vec![png_path, gif_path])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Palette};
+    use crate::template::viz::{Color, Frame, Style};
+
+    #[test]
+    fn parses_palettes() {
+        assert_eq!("dark".parse(), Ok(Palette::DARK));
+        assert_eq!(" light ".parse(), Ok(Palette::LIGHT));
+
+        let palette: Palette = "light,yellow=#ff0000, background=000000".parse().unwrap();
+        assert_eq!(palette.entries[0], [0, 0, 0]);
+        assert_eq!(palette.entries[5], [0xff, 0, 0]);
+        assert_eq!(palette.to_string(), "light,background=000000,yellow=ff0000");
+        assert_eq!(palette.to_string().parse(), Ok(palette));
+
+        assert!("blue".parse::<Palette>().is_err());
+        assert!("dark,orange=ff0000".parse::<Palette>().is_err());
+        assert!("dark,red=f00".parse::<Palette>().is_err());
+        assert!("dark,red".parse::<Palette>().is_err());
+    }
+
+    #[test]
+    fn draws_cells() {
+        let mut frame = Frame::new(1, 3);
+        frame.set((0, 0), '█', Style::fg(Color::Red));
+        frame.set((0, 1), '─', Style::fg(Color::Yellow).on(Color::Blue));
+        frame.set((0, 2), '#', Style::PLAIN);
+
+        let canvas = render(&frame, 4, 2, 3);
+        assert_eq!((canvas.width(), canvas.height()), (12, 8));
+
+        let (red, yellow, blue) = (
+            2 + Color::Red as u8,
+            2 + Color::Yellow as u8,
+            2 + Color::Blue as u8,
+        );
+        assert!((0..4).all(|x| (0..4).all(|y| canvas.pixel(x, y) == red)));
+        // A horizontal pipe through the middle of the cell.
+        assert!((4..8).all(|x| canvas.pixel(x, 1) == yellow));
+        assert_eq!(canvas.pixel(5, 0), blue);
+        assert_eq!(canvas.pixel(5, 3), blue);
+        // An inset square in the foreground colour.
+        assert_eq!(canvas.pixel(9, 1), 1);
+        // The second row of cells is padding.
+        assert_eq!(canvas.pixel(9, 5), 0);
+    }
+}
@@ -0,0 +1,533 @@
+/// Terminal visualizations for grid puzzles, enabled with `cargo solve <day> --viz`.
+/// A solution builds [`Frame`]s, i.e. snapshots of a grid with a style per cell, and passes them to a
+/// [`Visualizer`], which draws them in a box with ANSI colours and either animates or steps through them.
+/// Solutions opt in with `main!(<day>, viz)`, which calls their `visualize` function after both parts
+/// have run, so visualizations never affect timings.
+/// With `--viz-export`, the frames are written to `data/viz/` as images instead, see [`image`].
+use std::{
+    env,
+    io::{stdin, stdout, Write},
+    process, thread,
+    time::Duration,
+};
+
+use super::{config::Config, ANSI_RESET};
+use image::{ImageOptions, Palette};
+
+pub mod image;
+
+/// The eight standard terminal colours, plus gray for de-emphasized cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn ansi_foreground(self) -> u8 {
+        match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::Gray => 90,
+        }
+    }
+}
+
+/// How a cell is drawn. The default style uses the terminal's own colours.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style {
+        foreground: None,
+        background: None,
+        bold: false,
+    };
+
+    /// A style with the given foreground colour, e.g. `Style::fg(Color::Yellow).bold()`.
+    #[must_use]
+    pub const fn fg(color: Color) -> Self {
+        Style {
+            foreground: Some(color),
+            ..Self::PLAIN
+        }
+    }
+
+    #[must_use]
+    pub const fn on(self, color: Color) -> Self {
+        Style {
+            background: Some(color),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    fn ansi(self) -> String {
+        let mut codes = vec![];
+
+        if self.bold {
+            codes.push(1);
+        }
+        if let Some(color) = self.foreground {
+            codes.push(color.ansi_foreground());
+        }
+        if let Some(color) = self.background {
+            codes.push(color.ansi_foreground() + 10);
+        }
+
+        if codes.is_empty() {
+            String::new()
+        } else {
+            let codes = codes.iter().map(u8::to_string).collect::<Vec<_>>();
+            format!("\x1b[{}m", codes.join(";"))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub style: Style,
+}
+
+/// A snapshot of a grid, addressed by `(row, col)`, with an optional caption below it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Cell>,
+    caption: String,
+}
+
+impl Frame {
+    /// Creates a frame filled with blank cells.
+    #[must_use]
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let blank = Cell {
+            symbol: ' ',
+            style: Style::PLAIN,
+        };
+
+        Self {
+            rows,
+            cols,
+            cells: vec![blank; rows * cols],
+            caption: String::new(),
+        }
+    }
+
+    /// Creates a frame showing `text` unstyled, e.g. a puzzle input. Short lines are padded with blanks.
+    #[must_use]
+    pub fn from_text(text: &str) -> Self {
+        let lines = text.lines().collect::<Vec<&str>>();
+        let cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut frame = Self::new(lines.len(), cols);
+
+        for (row, line) in lines.iter().enumerate() {
+            for (col, symbol) in line.chars().enumerate() {
+                frame.cells[row * cols + col].symbol = symbol;
+            }
+        }
+
+        frame
+    }
+
+    /// Applies `style` to every cell, e.g. to dim the input before highlighting parts of it.
+    #[must_use]
+    pub fn styled(mut self, style: Style) -> Self {
+        for cell in &mut self.cells {
+            cell.style = style;
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn caption(&self) -> &str {
+        &self.caption
+    }
+
+    #[must_use]
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&Cell> {
+        (row < self.rows && col < self.cols).then(|| &self.cells[row * self.cols + col])
+    }
+
+    /// Replaces the cell at `(row, col)`.
+    ///
+    /// # Panics
+    /// Panics if the position lies outside of the frame.
+    pub fn set(&mut self, (row, col): (usize, usize), symbol: char, style: Style) {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) is outside of the frame"
+        );
+        self.cells[row * self.cols + col] = Cell { symbol, style };
+    }
+
+    /// Changes the style of the cell at `(row, col)`, keeping its symbol.
+    ///
+    /// # Panics
+    /// Panics if the position lies outside of the frame.
+    pub fn set_style(&mut self, (row, col): (usize, usize), style: Style) {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) is outside of the frame"
+        );
+        self.cells[row * self.cols + col].style = style;
+    }
+
+    pub fn set_caption(&mut self, caption: impl Into<String>) {
+        self.caption = caption.into();
+    }
+
+    /// Draws the frame in a box, with ANSI escape codes for styled cells.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let border = "─".repeat(self.cols);
+        let mut output = format!("┌{border}┐\n");
+
+        for row in self.cells.chunks(self.cols.max(1)).take(self.rows) {
+            output.push('│');
+            for cell in row {
+                let ansi = cell.style.ansi();
+                if ansi.is_empty() {
+                    output.push(cell.symbol);
+                } else {
+                    output.push_str(&format!("{ansi}{}{ANSI_RESET}", cell.symbol));
+                }
+            }
+            output.push_str("│\n");
+        }
+
+        output.push_str(&format!("└{border}┘\n"));
+
+        if !self.caption.is_empty() {
+            output.push_str(&self.caption);
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+/// Maps the pipe characters used by puzzle inputs (`|-LJ7F`) to box-drawing characters.
+/// Other characters are returned unchanged.
+#[must_use]
+pub fn box_drawing(symbol: char) -> char {
+    match symbol {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => symbol,
+    }
+}
+
+/// How frames are shown: animated at `fps` frames per second, or one at a time after pressing Enter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VizOptions {
+    pub fps: u32,
+    pub step: bool,
+    /// Write the frames to image files instead of showing them.
+    pub export: Option<ImageOptions>,
+}
+
+impl Default for VizOptions {
+    fn default() -> Self {
+        Self {
+            fps: 10,
+            step: false,
+            export: None,
+        }
+    }
+}
+
+impl VizOptions {
+    /// Reads `--viz`, `--viz-step`, `--viz-fps <n>`, `--viz-export`, `--viz-cell-size <px>` and
+    /// `--viz-palette <palette>`. All but the first imply `--viz`, the last two imply `--viz-export`.
+    /// Returns `None` if visualization is off.
+    pub fn from_arguments(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Self>, pico_args::Error> {
+        let enabled = args.contains("--viz");
+        let step = args.contains("--viz-step");
+        let fps: Option<u32> = args.opt_value_from_str("--viz-fps")?;
+        let export = args.contains("--viz-export");
+        let cell_size: Option<u32> = args.opt_value_from_str("--viz-cell-size")?;
+        let palette: Option<Palette> = args.opt_value_from_str("--viz-palette")?;
+
+        let export = (export || cell_size.is_some() || palette.is_some()).then(|| {
+            let default = ImageOptions::default();
+            ImageOptions {
+                cell_size: cell_size.unwrap_or(default.cell_size).max(1),
+                palette: palette.unwrap_or(default.palette),
+            }
+        });
+
+        if !(enabled || step || fps.is_some() || export.is_some()) {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            fps: fps.unwrap_or(Self::default().fps).max(1),
+            step,
+            export,
+        }))
+    }
+
+    /// The arguments that produce these options, for passing them on to a solution.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--viz".to_string(),
+            "--viz-fps".into(),
+            self.fps.to_string(),
+        ];
+        if self.step {
+            args.push("--viz-step".into());
+        }
+        if let Some(export) = self.export {
+            args.extend([
+                "--viz-export".into(),
+                "--viz-cell-size".into(),
+                export.cell_size.to_string(),
+                "--viz-palette".into(),
+                export.palette.to_string(),
+            ]);
+        }
+        args
+    }
+}
+
+/// Shows frames in the terminal or exports them, see the module documentation.
+pub struct Visualizer {
+    day: u8,
+    options: VizOptions,
+    /// Lines printed for the previous frame, which the next frame draws over when animating.
+    previous_lines: usize,
+    frames: usize,
+    /// Frames to export, kept until [`Visualizer::finish`].
+    recorded: Vec<Frame>,
+}
+
+impl Visualizer {
+    #[must_use]
+    pub fn new(day: u8, options: VizOptions) -> Self {
+        Self {
+            day,
+            options,
+            previous_lines: 0,
+            frames: 0,
+            recorded: vec![],
+        }
+    }
+
+    /// Creates a visualizer if the current process was started with `--viz`, ignoring other arguments.
+    #[must_use]
+    pub fn from_args(day: u8) -> Option<Self> {
+        let mut args = pico_args::Arguments::from_env();
+
+        match VizOptions::from_arguments(&mut args) {
+            Ok(options) => options.map(|options| Self::new(day, options)),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Number of frames shown so far.
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Shows a frame. When animating, it replaces the previous frame and stays up for `1 / fps` seconds.
+    /// When stepping, it waits for Enter; entering `c` plays the remaining frames as an animation.
+    pub fn emit(&mut self, frame: &Frame) {
+        if self.options.export.is_some() {
+            self.recorded.push(frame.clone());
+            self.frames += 1;
+            return;
+        }
+
+        let rendered = frame.render();
+        let mut stdout = stdout();
+
+        if self.frames > 0 && !self.options.step {
+            // Move the cursor to the start of the previous frame and clear everything below it.
+            print!("\x1b[{}F\x1b[J", self.previous_lines);
+        }
+
+        print!("{rendered}");
+        self.previous_lines = rendered.lines().count();
+        self.frames += 1;
+
+        if self.options.step {
+            print!(
+                "Frame {}: press Enter for the next one, or c and Enter to play the rest. ",
+                self.frames
+            );
+            let _ = stdout.flush();
+
+            let mut line = String::new();
+            // Without a terminal to read from, e.g. when stdin is closed, play the rest.
+            if matches!(stdin().read_line(&mut line), Ok(0) | Err(_)) || line.trim() == "c" {
+                self.options.step = false;
+                self.previous_lines += 1;
+            } else {
+                println!();
+            }
+        } else {
+            let _ = stdout.flush();
+            thread::sleep(Duration::from_secs(1) / self.options.fps);
+        }
+    }
+
+    /// Writes the recorded frames to `<day>.png` and `<day>.gif` in the viz data directory, `data/viz/`
+    /// by default, when exporting.
+    pub fn finish(self) {
+        let Some(export) = self.options.export else {
+            return;
+        };
+
+        let path = env::current_dir()
+            .unwrap()
+            .join(Config::get().data_dir("viz"))
+            .join(format!("{:02}", self.day));
+
+        match image::export(&self.recorded, &path, &export, self.options.fps) {
+            Ok(paths) => {
+                for path in paths {
+                    println!("Wrote {}", path.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to export visualization: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{box_drawing, Color, Frame, ImageOptions, Palette, Style, VizOptions};
+    use std::ffi::OsString;
+
+    fn args(s: &str) -> pico_args::Arguments {
+        pico_args::Arguments::from_vec(s.split_whitespace().map(OsString::from).collect())
+    }
+
+    #[test]
+    fn pads_text() {
+        let frame = Frame::from_text("ab\nc\n");
+        assert_eq!((frame.rows(), frame.cols()), (2, 2));
+        assert_eq!(frame.get((1, 0)).unwrap().symbol, 'c');
+        assert_eq!(frame.get((1, 1)).unwrap().symbol, ' ');
+        assert_eq!(frame.get((2, 0)), None);
+    }
+
+    #[test]
+    fn renders_boxed_frames() {
+        let mut frame = Frame::from_text("F7\nLJ");
+        frame.set_caption("loop");
+        assert_eq!(frame.render(), "┌──┐\n│F7│\n│LJ│\n└──┘\nloop\n");
+    }
+
+    #[test]
+    fn renders_styles() {
+        let mut frame = Frame::new(1, 2);
+        frame.set((0, 0), '#', Style::fg(Color::Yellow).bold());
+        frame.set((0, 1), '.', Style::fg(Color::Gray).on(Color::Blue));
+        assert_eq!(
+            frame.render(),
+            "┌──┐\n│\x1b[1;33m#\x1b[0m\x1b[90;44m.\x1b[0m│\n└──┘\n"
+        );
+    }
+
+    #[test]
+    fn maps_pipes() {
+        let pipes = "|-LJ7F.S".chars().map(box_drawing).collect::<String>();
+        assert_eq!(pipes, "│─└┘┐┌.S");
+    }
+
+    #[test]
+    fn parses_options() {
+        assert_eq!(
+            VizOptions::from_arguments(&mut args("--time")).unwrap(),
+            None
+        );
+        assert_eq!(
+            VizOptions::from_arguments(&mut args("--viz")).unwrap(),
+            Some(VizOptions::default())
+        );
+
+        let options = VizOptions::from_arguments(&mut args("--viz-fps 30 --viz-step"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            options,
+            VizOptions {
+                fps: 30,
+                step: true,
+                export: None,
+            }
+        );
+        assert_eq!(
+            VizOptions::from_arguments(&mut args(&options.to_args().join(" "))).unwrap(),
+            Some(options)
+        );
+    }
+
+    #[test]
+    fn parses_export_options() {
+        let options = VizOptions::from_arguments(&mut args("--viz-palette light,red=ff0000"))
+            .unwrap()
+            .unwrap();
+        let export = options.export.unwrap();
+        assert_eq!(export.cell_size, ImageOptions::default().cell_size);
+        assert_eq!(
+            export.palette,
+            Palette::LIGHT.with("red", [0xff, 0, 0]).unwrap()
+        );
+        assert_eq!(
+            VizOptions::from_arguments(&mut args(&options.to_args().join(" "))).unwrap(),
+            Some(options)
+        );
+
+        assert!(VizOptions::from_arguments(&mut args("--viz-palette pink")).is_err());
+    }
+}